@@ -0,0 +1,63 @@
+//! The days built on [BitGrid], and the grid itself against the `HashSet` it replaced.
+
+use std::{collections::HashSet, hint::black_box, time::Duration};
+
+use advent_of_code_2024::{bitgrid::BitGrid, days};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const SIZE: usize = 130;
+
+/// Every third cell of a puzzle-sized grid.
+fn cells() -> impl Iterator<Item = (usize, usize)> {
+    (0..SIZE * SIZE)
+        .step_by(3)
+        .map(|cell| (cell % SIZE, cell / SIZE))
+}
+
+fn occupancy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("occupancy");
+
+    group.bench_function("hash set", |b| {
+        b.iter(|| {
+            let set: HashSet<_> = cells().collect();
+            (0..SIZE)
+                .flat_map(|y| (0..SIZE).map(move |x| (x, y)))
+                .filter(|pos| set.contains(black_box(pos)))
+                .count()
+        })
+    });
+
+    group.bench_function("bit grid", |b| {
+        b.iter(|| {
+            let mut grid = BitGrid::new(SIZE, SIZE);
+            for (x, y) in cells() {
+                grid.insert(x, y);
+            }
+            (0..SIZE)
+                .flat_map(|y| (0..SIZE).map(move |x| (x, y)))
+                .filter(|&(x, y)| grid.contains(black_box(x), y))
+                .count()
+        })
+    });
+
+    group.finish();
+}
+
+fn solutions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solutions");
+    group.sample_size(10).warm_up_time(Duration::from_secs(1));
+
+    for (day, part) in [(6, 1), (6, 2), (12, 1), (12, 2), (14, 1), (14, 2)] {
+        let part = &days::get(day).expect("the day is enabled").parts[part - 1];
+        let params = part.params([]).expect("the defaults are valid");
+
+        group.bench_function(format!("day {day} part {}", part.part), |b| {
+            b.iter(|| part.solve(None, &params))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, occupancy, solutions);
+criterion_main!(benches);
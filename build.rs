@@ -0,0 +1,41 @@
+//! Fingerprints the sources, so that the answer cache (see `src/cache.rs`) tells apart the answers
+//! of different builds: a change to the code every day shares can change any day's answer.
+
+use std::{fs, io, path::Path};
+
+/// The same 64 bit FNV-1a hash as the cache's, continued from `hash`.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Hashes the path and the contents of every Rust file in `dir`, in the order of their paths.
+fn hash_dir(hash: u64, dir: &Path) -> io::Result<u64> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+
+    paths.into_iter().try_fold(hash, |hash, path| {
+        if path.is_dir() {
+            hash_dir(hash, &path)
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            let hash = fnv1a(hash, path.to_string_lossy().as_bytes());
+            Ok(fnv1a(hash, &fs::read(&path)?))
+        } else {
+            Ok(hash)
+        }
+    })
+}
+
+fn main() -> io::Result<()> {
+    println!("cargo::rerun-if-changed=src");
+    println!("cargo::rerun-if-changed=Cargo.toml");
+
+    let hash = fnv1a(0xcbf2_9ce4_8422_2325, &fs::read("Cargo.toml")?);
+    let hash = hash_dir(hash, Path::new("src"))?;
+    println!("cargo::rustc-env=AOC_SOURCE_HASH={hash:016x}");
+
+    Ok(())
+}
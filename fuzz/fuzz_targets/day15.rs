@@ -0,0 +1,13 @@
+#![no_main]
+
+use advent_of_code_2024::fuzzing;
+use advent_of_code_2024_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input<15>| {
+    fuzzing::parse(15, &input.bytes);
+
+    if let Some(structured) = &input.structured {
+        fuzzing::solve(15, structured);
+    }
+});
@@ -0,0 +1,33 @@
+//! Inputs for the fuzz targets: the raw bytes for the parsers, plus a small, puzzle-shaped input
+//! built out of those same bytes for the solvers.
+
+use std::fmt::{self, Debug};
+
+use advent_of_code_2024::generate;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+pub struct Input<const DAY: u8> {
+    pub bytes: Vec<u8>,
+    pub structured: Option<String>,
+}
+
+impl<'a, const DAY: u8> Arbitrary<'a> for Input<DAY> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bytes = u.peek_bytes(u.len()).unwrap_or_default().to_vec();
+        let structured = generate::input(DAY, u).ok();
+
+        Ok(Self { bytes, structured })
+    }
+}
+
+/// Shows the inputs as text, so `cargo fuzz fmt` prints what to save in `fuzz/regressions/`.
+impl<const DAY: u8> Debug for Input<DAY> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "bytes:\n{}", String::from_utf8_lossy(&self.bytes))?;
+
+        match &self.structured {
+            Some(input) => writeln!(f, "structured:\n{input}"),
+            None => writeln!(f, "structured: not enough data"),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+"""Smoke tests of the extension module against the puzzles' examples."""
+
+import pytest
+
+import aoc2024
+
+REPORTS = """\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+"""
+
+GARDEN = """\
+AAAA
+BBCD
+BBCC
+EEEC
+"""
+
+MACHINES = """\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+"""
+
+ROBOTS = """\
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+"""
+
+
+def test_reports():
+    reports = aoc2024.day2.parse(REPORTS)
+    assert [len(report) for report in reports] == [5] * 6
+    assert reports[0].levels == [7, 6, 4, 2, 1]
+    assert isinstance(reports[0], aoc2024.Report)
+
+    assert aoc2024.day2.part_1(REPORTS) == 2
+    assert aoc2024.day2.part_2(REPORTS) == 4
+
+
+def test_regions():
+    regions = aoc2024.day12.parse(GARDEN)
+    summary = sorted((r.plant, r.area, r.perimeter, r.sides) for r in regions)
+    assert summary == [
+        ("A", 4, 10, 4),
+        ("B", 4, 8, 4),
+        ("C", 4, 10, 8),
+        ("D", 1, 4, 4),
+        ("E", 3, 8, 4),
+    ]
+    assert regions[0].cells == [(0, 0), (1, 0), (2, 0), (3, 0)]
+
+    assert aoc2024.day12.part_1(GARDEN) == 140
+    assert aoc2024.day12.part_2(GARDEN) == 80
+
+    # the grid starts after the blank lines around it
+    regions = aoc2024.day12.parse("\n\n" + GARDEN)
+    assert sorted(r.plant for r in regions) == ["A", "B", "C", "D", "E"]
+
+
+def test_machines():
+    machines = aoc2024.day13.parse(MACHINES)
+    assert len(machines) == 4
+    assert machines[0].button_a == (94, 34)
+    assert machines[0].button_b == (22, 67)
+    assert machines[0].prize == (8400, 5400)
+
+    assert aoc2024.day13.part_1(MACHINES) == 480
+
+
+def test_robots():
+    robots = aoc2024.day14.parse(ROBOTS)
+    assert len(robots) == 12
+    assert robots[0].position == (0, 4)
+    assert robots[0].velocity == (3, -3)
+
+    assert aoc2024.day14.part_1(ROBOTS, width=11, height=7) == 12
+    assert aoc2024.solve(14, 1, ROBOTS, width=11, height=7) == 12
+
+
+def test_plain_parsers():
+    assert aoc2024.day1.parse("3   4\n4   3\n") == ([3, 4], [4, 3])
+    assert aoc2024.day9.parse("12345") == [(0, 1), (None, 2), (1, 3), (None, 4), (2, 5)]
+    assert aoc2024.day11.parse("125 17") == [125, 17]
+    assert aoc2024.day8.parse("..a\n.a.\n")["antennas"] == {"a": [(2, 0), (1, 1)]}
+
+
+def test_errors():
+    with pytest.raises(ValueError):
+        aoc2024.day14.parse("p=0,4 v=3")
+    with pytest.raises(ValueError):
+        aoc2024.day14.part_1(ROBOTS, width=1)
+    with pytest.raises(ValueError):
+        aoc2024.day14.part_1(ROBOTS, depth=3)
+    with pytest.raises(ValueError):
+        aoc2024.solve(26, 1)
+    # the example's robots never draw the picture
+    with pytest.raises(ValueError):
+        aoc2024.day14.part_2(ROBOTS, width=11, height=7)
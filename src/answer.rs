@@ -0,0 +1,202 @@
+use std::{
+    fmt::{self, Display},
+    num::TryFromIntError,
+};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+/// The answer to a puzzle part.
+///
+/// Most puzzles have numeric answers, but some ask for a string (e.g. a comma-separated list or a
+/// password) or have an answer that has to be read off some ASCII art, which is kept as lines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    Lines(Vec<String>),
+}
+
+impl Answer {
+    pub fn as_number(&self) -> Option<i128> {
+        match self {
+            Self::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Text(text) => write!(f, "{text}"),
+            Self::Lines(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+/// Implemented by hand since untagged enums can't deserialize `i128`s.
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswerVisitor;
+
+        impl<'de> Visitor<'de> for AnswerVisitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an integer, a string or a list of lines")
+            }
+
+            fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+                Ok(n.into())
+            }
+
+            fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+                Ok(n.into())
+            }
+
+            fn visit_i128<E: de::Error>(self, n: i128) -> Result<Answer, E> {
+                Ok(n.into())
+            }
+
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Answer, E> {
+                Ok(text.into())
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Answer, A::Error> {
+                let mut lines = vec![];
+                while let Some(line) = seq.next_element()? {
+                    lines.push(line);
+                }
+
+                Ok(Answer::Lines(lines))
+            }
+        }
+
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+/// `From` for the integers which always fit in an `i128`.
+macro_rules! impl_number_conversions {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(n: $ty) -> Self {
+                    Self::Number(i128::try_from(n).expect("answer doesn't fit in an i128"))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_number_comparisons {
+    ($($ty:ty),*) => {
+        $(
+            impl PartialEq<$ty> for Answer {
+                fn eq(&self, other: &$ty) -> bool {
+                    i128::try_from(*other).is_ok_and(|other| self.as_number() == Some(other))
+                }
+            }
+        )*
+    };
+}
+
+impl_number_conversions!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+impl_number_comparisons!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Fails for numbers above `i128::MAX`, which an answer can't hold.
+impl TryFrom<u128> for Answer {
+    type Error = TryFromIntError;
+
+    fn try_from(n: u128) -> Result<Self, Self::Error> {
+        i128::try_from(n).map(Self::Number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Self::Lines(lines)
+    }
+}
+
+/// Compares against the displayed answer, so multi-line answers can be checked against a block of
+/// text.
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Self::Number(_) => false,
+            Self::Text(text) => text == other,
+            Self::Lines(lines) => lines.join("\n") == *other,
+        }
+    }
+}
+
+impl PartialEq<String> for Answer {
+    fn eq(&self, other: &String) -> bool {
+        self == &other.as_str()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answer;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(-42isize).to_string(), "-42");
+        assert_eq!(Answer::from("4,6,3,5").to_string(), "4,6,3,5");
+        assert_eq!(
+            Answer::from(vec!["#..#".to_string(), ".##.".to_string()]).to_string(),
+            "#..#\n.##."
+        );
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(Answer::from(11u16), 11);
+        assert_eq!(Answer::from(11u16), 11usize);
+        assert_eq!(Answer::from(u64::MAX), u64::MAX);
+        assert_ne!(Answer::from(-1isize), u64::MAX);
+        assert_eq!(Answer::try_from(7u128), Ok(Answer::from(7u8)));
+        assert!(Answer::try_from(u128::MAX).is_err());
+        assert_ne!(Answer::from(i128::MAX), u128::MAX);
+        assert_eq!(Answer::from("abc"), "abc");
+        assert_ne!(Answer::from(1u8), "1");
+        assert_eq!(
+            Answer::from(vec!["ab".to_string(), "cd".to_string()]),
+            "ab\ncd"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let answers = [
+            Answer::from(31usize),
+            Answer::from("4,6,3,5"),
+            Answer::from(vec!["#.".to_string(), ".#".to_string()]),
+        ];
+
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r###"[31,"4,6,3,5",["#.",".#"]]"###);
+
+        let parsed: Vec<Answer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, answers);
+    }
+}
@@ -0,0 +1,334 @@
+//! Runs every day against several users' inputs, laid out as
+//!
+//! ```text
+//! inputs/
+//!   <user>/
+//!     day1.input
+//!     ...
+//!     answers.json
+//! ```
+//!
+//! where `answers.json` holds the answers the user already knows, by day and part, e.g.
+//! `{"1": [1530215, 26800609], "11": [220722, null]}`. Each answer is checked against them, so
+//! that solutions relying on the quirks of one input surface immediately.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+    time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::{
+    answer::Answer,
+    cache::{self, Cache},
+    days,
+};
+
+pub const ANSWERS_FILE: &str = "answers.json";
+
+#[derive(Debug)]
+pub struct User {
+    pub name: String,
+    pub inputs: BTreeMap<u8, String>,
+    pub answers: HashMap<u8, [Option<Answer>; 2]>,
+}
+
+impl User {
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let name = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut inputs = BTreeMap::new();
+        for day in days::DAYS {
+            match fs::read_to_string(dir.join(format!("day{}.input", day.day))) {
+                Ok(input) => {
+                    inputs.insert(day.day, input);
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        let answers = match fs::read_to_string(dir.join(ANSWERS_FILE)) {
+            Ok(answers) => serde_json::from_str(&answers).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid {}: {err}", dir.join(ANSWERS_FILE).display()),
+                )
+            })?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(Self {
+            name,
+            inputs,
+            answers,
+        })
+    }
+
+    pub(crate) fn expected(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&day)?.get(usize::from(part) - 1)?.as_ref()
+    }
+}
+
+/// Loads every user in `dir`, sorted by name.
+pub fn load_users(dir: &Path) -> io::Result<Vec<User>> {
+    let mut users = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .filter(|path| path.as_ref().map_or(true, |path| path.is_dir()))
+        .map(|path| User::load(&path?))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    users.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(users)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum Status {
+    Correct,
+    Wrong {
+        expected: Answer,
+    },
+    /// The user doesn't know the answer yet.
+    Unchecked,
+    InvalidInput {
+        error: String,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Outcome {
+    pub user: String,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    pub duration_ms: Option<f64>,
+    #[serde(flatten)]
+    pub status: Status,
+}
+
+impl Outcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status, Status::Correct | Status::Unchecked)
+    }
+}
+
+/// Runs every part of every day each user has an input for, taking the answers already in `cache`.
+pub fn run(users: &[User], timeout: Option<Duration>, cache: Option<&Cache>) -> Vec<Outcome> {
+    let mut outcomes = vec![];
+
+    for user in users {
+        for (&day, input) in &user.inputs {
+            let day = days::get(day).expect("inputs are only loaded for known days");
+
+            for part in &day.parts {
+                let expected = user.expected(day.day, part.part);
+                let mut outcome = Outcome {
+                    user: user.name.clone(),
+                    day: day.day,
+                    part: part.part,
+                    answer: None,
+                    duration_ms: None,
+                    status: Status::Unchecked,
+                };
+
+                if let Err(err) = (day.validate)(input) {
+                    outcome.status = Status::InvalidInput {
+                        error: err.to_string(),
+                    };
+                    outcomes.push(outcome);
+                    continue;
+                }
+
+                let params = part.params([]).expect("the defaults are valid");
+                match cache::run(cache, day, part, Some(input), &params, timeout, None) {
+                    Ok(run) => {
+                        outcome.status = match expected {
+                            None => Status::Unchecked,
+                            Some(expected) if *expected == run.answer => Status::Correct,
+                            Some(expected) => Status::Wrong {
+                                expected: expected.clone(),
+                            },
+                        };
+                        outcome.answer = Some(run.answer);
+                        outcome.duration_ms = Some(run.duration.as_secs_f64() * 1000.0);
+                    }
+                    Err(failure) => {
+                        outcome.status = Status::Failed {
+                            error: failure.to_string(),
+                        };
+                    }
+                }
+
+                outcomes.push(outcome);
+            }
+        }
+    }
+
+    outcomes
+}
+
+/// The outcomes as a table with a row per day and part and a column per user.
+pub struct Matrix<'a>(pub &'a [Outcome]);
+
+impl Matrix<'_> {
+    fn cell(outcome: &Outcome) -> String {
+        let answer = outcome
+            .answer
+            .as_ref()
+            .map(|answer| answer.to_string().replace('\n', " "))
+            .unwrap_or_default();
+        let duration = outcome
+            .duration_ms
+            .map(|ms| format!(" ({ms:.1}ms)"))
+            .unwrap_or_default();
+
+        match &outcome.status {
+            Status::Correct => format!("✓ {answer}{duration}"),
+            Status::Unchecked => format!("? {answer}{duration}"),
+            Status::Wrong { expected } => format!("✗ {answer}, expected {expected}{duration}"),
+            Status::InvalidInput { error } => format!("✗ invalid input: {error}"),
+            Status::Failed { error } => format!("✗ {error}"),
+        }
+    }
+}
+
+impl Display for Matrix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut users: Vec<&str> = vec![];
+        let mut rows: BTreeMap<(u8, u8), HashMap<&str, String>> = BTreeMap::new();
+        for outcome in self.0 {
+            if !users.contains(&outcome.user.as_str()) {
+                users.push(&outcome.user);
+            }
+            rows.entry((outcome.day, outcome.part))
+                .or_default()
+                .insert(&outcome.user, Self::cell(outcome));
+        }
+
+        let header = ["part".to_string()]
+            .into_iter()
+            .chain(users.iter().map(|user| user.to_string()));
+        let lines: Vec<Vec<String>> = [header.collect()]
+            .into_iter()
+            .chain(rows.iter().map(|(&(day, part), cells)| {
+                let cells = users
+                    .iter()
+                    .map(|user| cells.get(user).cloned().unwrap_or_else(|| "-".to_string()));
+                [format!("{day}.{part}")].into_iter().chain(cells).collect()
+            }))
+            .collect();
+
+        let widths: Vec<usize> = (0..=users.len())
+            .map(|column| {
+                lines
+                    .iter()
+                    .map(|line| line[column].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for line in &lines {
+            let cells: Vec<_> = line
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:<width$}"))
+                .collect();
+            writeln!(f, "{}", cells.join("  ").trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+// the tests use the examples of several days
+#[cfg(all(test, feature = "all"))]
+mod test {
+    use std::{env, fs, path::PathBuf};
+
+    use super::{load_users, run, Matrix, Status};
+
+    /// A fresh directory with two users, one of them expecting a wrong answer for day 1 part 2.
+    fn inputs_dir() -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        for (user, day1, answers) in [
+            (
+                "alice",
+                "3   4\n4   3\n2   5\n1   3\n3   9\n3   3",
+                r#"{"1": [11, 31]}"#,
+            ),
+            (
+                "bob",
+                "1   1\n2   2",
+                r#"{"1": [0, 42], "11": [55312, null]}"#,
+            ),
+        ] {
+            fs::create_dir_all(dir.join(user)).unwrap();
+            fs::write(dir.join(user).join("day1.input"), day1).unwrap();
+            fs::write(dir.join(user).join("answers.json"), answers).unwrap();
+        }
+        fs::write(dir.join("bob/day11.input"), "125 17").unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn batch() {
+        let dir = inputs_dir();
+        let users = load_users(&dir).unwrap();
+        assert_eq!(users.len(), 2);
+
+        let outcomes = run(&users, None, None);
+        let statuses: Vec<_> = outcomes
+            .iter()
+            .map(|outcome| {
+                (
+                    outcome.user.as_str(),
+                    outcome.day,
+                    outcome.part,
+                    &outcome.status,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            statuses,
+            [
+                ("alice", 1, 1, &Status::Correct),
+                ("alice", 1, 2, &Status::Correct),
+                ("bob", 1, 1, &Status::Correct),
+                (
+                    "bob",
+                    1,
+                    2,
+                    &Status::Wrong {
+                        expected: 42.into()
+                    }
+                ),
+                ("bob", 11, 1, &Status::Correct),
+                ("bob", 11, 2, &Status::Unchecked),
+            ]
+        );
+
+        let matrix = Matrix(&outcomes).to_string();
+        let lines: Vec<_> = matrix.lines().collect();
+        assert!(lines[0].starts_with("part  alice"));
+        assert!(lines[3].starts_with("11.1  -"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
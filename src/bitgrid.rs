@@ -0,0 +1,344 @@
+//! A dense set of grid cells, one bit per cell.
+//!
+//! Each row is stored as whole `u64` words, so that operations on entire grids ([BitGrid::shifted],
+//! `&`, `|` and `-`) work a word (64 cells) at a time instead of cell by cell. The bits past the
+//! width in a row's last word are always zero.
+
+use std::{
+    fmt::{self, Debug},
+    ops::{BitAnd, BitOr, Sub},
+};
+
+const BITS: usize = u64::BITS as usize;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// Words per row.
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// An empty grid.
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(BITS);
+        Self {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, x: usize, y: usize) -> (usize, u64) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside of the {}×{} grid",
+            self.width,
+            self.height
+        );
+        (y * self.stride + x / BITS, 1 << (x % BITS))
+    }
+
+    /// Whether the cell is set, cells outside of the grid never are.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+
+        let (word, bit) = self.index(x, y);
+        self.words[word] & bit != 0
+    }
+
+    /// Sets the cell, returning whether it wasn't set before.
+    pub fn insert(&mut self, x: usize, y: usize) -> bool {
+        let (word, bit) = self.index(x, y);
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        inserted
+    }
+
+    /// Clears the cell, returning whether it was set before.
+    pub fn remove(&mut self, x: usize, y: usize) -> bool {
+        let (word, bit) = self.index(x, y);
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    /// Clears every cell.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// The number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// The words of row `y`, with cell `x` at bit `x % 64` of word `x / 64`.
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    /// The set cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(move |y| {
+            self.row(y).iter().enumerate().flat_map(move |(i, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    (word != 0).then(|| {
+                        let bit = word.trailing_zeros() as usize;
+                        word &= word - 1;
+                        (i * BITS + bit, y)
+                    })
+                })
+            })
+        })
+    }
+
+    /// The grid moved by `(dx, dy)`: cell `(x, y)` of the result is cell `(x - dx, y - dy)` of this
+    /// grid. Cells moved out of the grid are dropped, and the ones moved in are empty.
+    pub fn shifted(&self, dx: isize, dy: isize) -> Self {
+        let mut shifted = Self::new(self.width, self.height);
+
+        for y in 0..self.height {
+            let Some(from_y) = y
+                .checked_add_signed(-dy)
+                .filter(|&from_y| from_y < self.height)
+            else {
+                continue;
+            };
+
+            let row = self.row(from_y);
+            let to = &mut shifted.words[y * self.stride..(y + 1) * self.stride];
+            shift_row(row, to, dx);
+        }
+
+        shifted.clear_padding();
+        shifted
+    }
+
+    /// Clears the bits past the width, which shifting to the right might have set.
+    fn clear_padding(&mut self) {
+        let used = self.width % BITS;
+        if used == 0 {
+            return;
+        }
+
+        let mask = (1 << used) - 1;
+        for row in self.words.chunks_mut(self.stride) {
+            *row.last_mut().expect("rows of a non-empty grid have words") &= mask;
+        }
+    }
+
+    fn zip_words(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "the grids have different sizes"
+        );
+
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| op(a, b))
+                .collect(),
+            ..*self
+        }
+    }
+}
+
+/// Writes `from` moved by `dx` cells into `to`, which has the same length.
+fn shift_row(from: &[u64], to: &mut [u64], dx: isize) {
+    let (words, bits) = (dx.unsigned_abs() / BITS, dx.unsigned_abs() % BITS);
+
+    for (i, to) in to.iter_mut().enumerate() {
+        // the two words the bits of `to` come from, the lower one first
+        let (low, high) = if dx >= 0 {
+            let high = i.checked_sub(words);
+            (high.and_then(|high| high.checked_sub(1)), high)
+        } else {
+            (Some(i + words), Some(i + words + 1))
+        };
+        let word = |index: Option<usize>| index.and_then(|index| from.get(index)).copied();
+        let (low, high) = (word(low).unwrap_or(0), word(high).unwrap_or(0));
+
+        *to = match (dx >= 0, bits) {
+            (true, 0) => high,
+            (true, _) => high << bits | low >> (BITS - bits),
+            (false, 0) => low,
+            (false, _) => low >> bits | high << (BITS - bits),
+        };
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: Self) -> BitGrid {
+        self.zip_words(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: Self) -> BitGrid {
+        self.zip_words(rhs, |a, b| a | b)
+    }
+}
+
+/// The cells set in the left grid but not in the right one.
+impl Sub for &BitGrid {
+    type Output = BitGrid;
+
+    fn sub(self, rhs: Self) -> BitGrid {
+        self.zip_words(rhs, |a, b| a & !b)
+    }
+}
+
+impl FromIterator<(usize, usize)> for BitGrid {
+    /// A grid just large enough for the cells.
+    fn from_iter<T: IntoIterator<Item = (usize, usize)>>(cells: T) -> Self {
+        let cells: Vec<_> = cells.into_iter().collect();
+        let width = cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let height = cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+
+        let mut grid = Self::new(width, height);
+        for (x, y) in cells {
+            grid.insert(x, y);
+        }
+        grid
+    }
+}
+
+impl Debug for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "BitGrid {}×{}", self.width, self.height)?;
+        for y in 0..self.height {
+            let row: String = (0..self.width)
+                .map(|x| if self.contains(x, y) { '#' } else { '.' })
+                .collect();
+            writeln!(f, "{row}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::BitGrid;
+
+    /// A grid wider than a word, with cells on both sides of the word boundary.
+    fn grid() -> BitGrid {
+        let mut grid = BitGrid::new(130, 3);
+        for (x, y) in [(0, 0), (63, 0), (64, 1), (65, 1), (127, 2), (129, 2)] {
+            grid.insert(x, y);
+        }
+        grid
+    }
+
+    #[test]
+    fn cells() {
+        let mut grid = grid();
+        assert_eq!(grid.count_ones(), 6);
+        assert!(grid.contains(63, 0));
+        assert!(!grid.contains(64, 0));
+        assert!(!grid.contains(130, 2));
+        assert!(!grid.contains(0, 3));
+
+        assert!(!grid.insert(63, 0));
+        assert!(grid.remove(63, 0));
+        assert!(!grid.remove(63, 0));
+        assert_eq!(grid.count_ones(), 5);
+
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            [(0, 0), (64, 1), (65, 1), (127, 2), (129, 2)]
+        );
+
+        grid.clear();
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    #[should_panic = "(130, 0) is outside of the 130×3 grid"]
+    fn outside() {
+        grid().insert(130, 0);
+    }
+
+    #[test]
+    fn shifting() {
+        let grid = grid();
+        let cells: HashSet<_> = grid.iter().collect();
+
+        for (dx, dy) in [
+            (0, 0),
+            (1, 0),
+            (-1, 0),
+            (63, 1),
+            (-64, 0),
+            (65, -1),
+            (-129, 0),
+            (2, 5),
+        ] {
+            let expected: HashSet<_> = cells
+                .iter()
+                .map(|&(x, y)| (x as isize + dx, y as isize + dy))
+                .filter(|&(x, y)| (0..130).contains(&x) && (0..3).contains(&y))
+                .map(|(x, y)| (x as usize, y as usize))
+                .collect();
+
+            let shifted = grid.shifted(dx, dy);
+            assert_eq!(
+                shifted.iter().collect::<HashSet<_>>(),
+                expected,
+                "shifted by ({dx}, {dy})"
+            );
+            assert_eq!(shifted.count_ones(), expected.len());
+        }
+    }
+
+    #[test]
+    fn operators() {
+        let grid = grid();
+        let other = grid.shifted(1, 0);
+
+        assert_eq!((&grid & &other).iter().collect::<Vec<_>>(), [(65, 1)]);
+        assert_eq!((&grid | &other).count_ones(), 10);
+        assert_eq!(
+            (&grid - &other).iter().collect::<Vec<_>>(),
+            [(0, 0), (63, 0), (64, 1), (127, 2), (129, 2)]
+        );
+    }
+
+    #[test]
+    fn collecting() {
+        let grid: BitGrid = [(2, 0), (0, 1)].into_iter().collect();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(format!("{grid:?}"), "BitGrid 3×2\n..#\n#..\n");
+    }
+}
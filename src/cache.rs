@@ -0,0 +1,236 @@
+//! An on-disk cache of answers, so that slow parts don't run again on inputs they already solved.
+//!
+//! An answer is keyed by the day, the part, its parameters, a hash of the input and a fingerprint
+//! of the sources the binary was built from, so changing any of them computes it anew. Each answer
+//! is a JSON file named after its key, and only successful runs are cached.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answer::Answer,
+    cancel::Failure,
+    days::{Day, Params, Part, Run},
+    progress::Tracker,
+};
+
+/// A 64 bit FNV-1a hash, which unlike the standard library's hashers stays the same across Rust
+/// versions.
+fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Identifies an answer in the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    day: u8,
+    part: u8,
+    hash: u64,
+}
+
+impl Key {
+    pub fn new(day: &Day, part: &Part, params: &Params, input: Option<&str>) -> Self {
+        let params: String = params
+            .iter()
+            .map(|(name, value)| format!("{name}={value};"))
+            .collect();
+        let input = input.unwrap_or(day.input);
+
+        let hashes = [
+            // every source file and Cargo.toml, see build.rs
+            hash(env!("AOC_SOURCE_HASH").as_bytes()),
+            hash(params.as_bytes()),
+            hash(input.as_bytes()),
+        ];
+        let hash = hash(&hashes.map(u64::to_le_bytes).concat());
+
+        Self {
+            day: day.day,
+            part: part.part,
+            hash,
+        }
+    }
+
+    fn file_name(&self) -> String {
+        format!("day{}-part{}-{:016x}.json", self.day, self.part, self.hash)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    answer: Answer,
+    duration_ms: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The cached run, with the duration of the run which computed it. Entries which can't be read
+    /// are treated as missing.
+    pub fn get(&self, key: &Key) -> Option<Run> {
+        let entry = fs::read_to_string(self.dir.join(key.file_name())).ok()?;
+        let entry: Entry = serde_json::from_str(&entry).ok()?;
+
+        Some(Run {
+            answer: entry.answer,
+            duration: Duration::try_from_secs_f64(entry.duration_ms / 1000.0).ok()?,
+        })
+    }
+
+    pub fn put(&self, key: &Key, run: &Run) -> io::Result<()> {
+        let entry = Entry {
+            answer: run.answer.clone(),
+            duration_ms: run.duration.as_secs_f64() * 1000.0,
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.dir.join(key.file_name()),
+            serde_json::to_string(&entry).expect("answers can be serialized"),
+        )
+    }
+
+    /// Removes every cached answer, returning how many there were. Other files in the directory
+    /// are left alone.
+    pub fn clear(&self) -> io::Result<usize> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(err),
+        };
+
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry?.path();
+            let is_answer = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("day") && name.ends_with(".json"));
+
+            if is_answer {
+                fs::remove_file(path)?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+/// [Part::run], but answered from `cache` when possible. Failing to write to the cache doesn't fail
+/// the run.
+pub fn run(
+    cache: Option<&Cache>,
+    day: &Day,
+    part: &Part,
+    input: Option<&str>,
+    params: &Params,
+    timeout: Option<Duration>,
+    tracker: Option<Arc<Tracker>>,
+) -> Result<Run, Failure> {
+    let Some(cache) = cache else {
+        return part.run(input, params, timeout, tracker);
+    };
+
+    let key = Key::new(day, part, params, input);
+    if let Some(run) = cache.get(&key) {
+        return Ok(run);
+    }
+
+    let run = part.run(input, params, timeout, tracker)?;
+    let _ = cache.put(&key, &run);
+    Ok(run)
+}
+
+#[cfg(all(test, feature = "day11"))]
+mod test {
+    use std::{env, fs, time::Duration};
+
+    use super::{hash, run, Cache, Key};
+    use crate::days::{self, Run};
+
+    fn cache(name: &str) -> Cache {
+        let dir = env::temp_dir().join(format!("aoc-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Cache::new(dir)
+    }
+
+    #[test]
+    fn hashing() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn keys() {
+        let day = days::get(11).unwrap();
+        let part = &day.parts[0];
+        let params = part.params([]).unwrap();
+
+        let key = Key::new(day, part, &params, Some("125 17"));
+        assert_eq!(key, Key::new(day, part, &params, Some("125 17")));
+        assert_ne!(key, Key::new(day, part, &params, Some("125 18")));
+        assert_ne!(key, Key::new(day, part, &params, None));
+        assert_ne!(
+            key,
+            Key::new(
+                day,
+                part,
+                &part.params([("blinks", "6")]).unwrap(),
+                Some("125 17")
+            )
+        );
+        assert_ne!(key, Key::new(day, &day.parts[1], &params, Some("125 17")));
+    }
+
+    #[test]
+    fn caching() {
+        let cache = cache("caching");
+        let day = days::get(11).unwrap();
+        let part = &day.parts[0];
+        let params = part.params([]).unwrap();
+        let key = Key::new(day, part, &params, Some("125 17"));
+
+        assert_eq!(cache.get(&key), None);
+        let computed = run(Some(&cache), day, part, Some("125 17"), &params, None, None).unwrap();
+        assert_eq!(computed.answer.as_number(), Some(55312));
+        assert_eq!(cache.get(&key).unwrap().answer.as_number(), Some(55312));
+
+        // whatever is in the cache wins
+        let fake = Run {
+            answer: 42.into(),
+            duration: Duration::from_millis(3),
+        };
+        cache.put(&key, &fake).unwrap();
+        let cached = run(Some(&cache), day, part, Some("125 17"), &params, None, None).unwrap();
+        assert_eq!(cached, fake);
+        let uncached = run(None, day, part, Some("125 17"), &params, None, None).unwrap();
+        assert_eq!(uncached.answer.as_number(), Some(55312));
+
+        fs::write(cache.dir().join("notes.txt"), "keep me").unwrap();
+        assert_eq!(cache.clear().unwrap(), 1);
+        assert_eq!(cache.get(&key), None);
+        assert!(cache.dir().join("notes.txt").exists());
+
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+}
@@ -0,0 +1,172 @@
+//! Cooperative cancellation of long-running solutions.
+//!
+//! Solutions call [check] in their long loops. When a solution runs under [with_timeout] and the
+//! timeout has passed, [check] unwinds the solution's thread, and [with_timeout] reports a
+//! [Failure::TimedOut] instead of hanging. This way the token doesn't have to be threaded through
+//! every solution's functions, and [check] is a no-op when nothing is waiting for a timeout.
+
+use std::{
+    any::Any,
+    cell::OnceCell,
+    error::Error,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Solutions run on their own thread, with as much stack as the main thread would have.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+thread_local! {
+    static TOKEN: OnceCell<Arc<AtomicBool>> = const { OnceCell::new() };
+}
+
+/// The payload the solution's thread unwinds with once it's cancelled.
+struct Cancelled;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    TimedOut(Duration),
+    Panicked(String),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TimedOut(timeout) => write!(f, "timed out after {timeout:?}"),
+            Self::Panicked(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+impl Error for Failure {}
+
+/// Stops the current solution if it has been cancelled.
+pub fn check() {
+    let cancelled = TOKEN.with(|token| {
+        token
+            .get()
+            .is_some_and(|cancelled| cancelled.load(Ordering::Relaxed))
+    });
+
+    if cancelled {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs `solve` on its own thread and cancels it once `timeout` has passed. A solution which
+/// doesn't [check] in time still counts as timed out once it finishes. Panics are caught and
+/// reported as a [Failure::Panicked].
+pub fn with_timeout<T: Send>(
+    timeout: Option<Duration>,
+    solve: impl FnOnce() -> T + Send,
+) -> Result<T, Failure> {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (done, finished) = mpsc::channel();
+    let start = Instant::now();
+
+    let (result, timed_out) = thread::scope(|scope| {
+        let token = cancelled.clone();
+        let solution = thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, move || {
+                let _ = TOKEN.with(|cell| cell.set(token));
+                let result = panic::catch_unwind(AssertUnwindSafe(solve));
+                let _ = done.send(());
+                result
+            })
+            .expect("couldn't spawn a thread for the solution");
+
+        let timed_out = match timeout {
+            Some(timeout) => {
+                let left = timeout.saturating_sub(start.elapsed());
+                finished.recv_timeout(left).is_err() || start.elapsed() > timeout
+            }
+            None => finished.recv().is_err(),
+        };
+        if timed_out {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+
+        let result = solution
+            .join()
+            .expect("panics are caught on the solution's thread");
+        (result, timed_out)
+    });
+
+    if let (true, Some(timeout)) = (timed_out, timeout) {
+        return Err(Failure::TimedOut(timeout));
+    }
+
+    result.map_err(|payload| match (payload.is::<Cancelled>(), timeout) {
+        (true, Some(timeout)) => Failure::TimedOut(timeout),
+        (true, None) => Failure::Panicked("cancelled without a timeout".to_string()),
+        (false, _) => Failure::Panicked(panic_message(payload)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{thread, time::Duration};
+
+    use super::{check, with_timeout, Failure};
+
+    const TIMEOUT: Option<Duration> = Some(Duration::from_millis(50));
+
+    #[test]
+    fn finishes() {
+        assert_eq!(with_timeout(TIMEOUT, || 42), Ok(42));
+        assert_eq!(with_timeout(None, || 42), Ok(42));
+    }
+
+    #[test]
+    fn times_out() {
+        let result = with_timeout(TIMEOUT, || loop {
+            check();
+        });
+
+        assert_eq!(result, Err(Failure::TimedOut(TIMEOUT.unwrap())));
+    }
+
+    #[test]
+    fn times_out_without_checking() {
+        let result = with_timeout(TIMEOUT, || {
+            thread::sleep(TIMEOUT.unwrap() * 3);
+            42
+        });
+
+        assert_eq!(result, Err(Failure::TimedOut(TIMEOUT.unwrap())));
+    }
+
+    #[test]
+    fn times_out_immediately() {
+        let result = with_timeout(Some(Duration::ZERO), || 42);
+        assert_eq!(result, Err(Failure::TimedOut(Duration::ZERO)));
+    }
+
+    #[test]
+    fn panics() {
+        let result = with_timeout(TIMEOUT, || -> u8 { panic!("Invalid input") });
+        assert_eq!(result, Err(Failure::Panicked("Invalid input".to_string())));
+    }
+
+    #[test]
+    fn check_outside_of_a_timeout() {
+        check();
+    }
+}
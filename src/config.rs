@@ -0,0 +1,262 @@
+//! The project configuration, read from the first `aoc.toml` found in the working directory or one
+//! of its parents:
+//!
+//! ```toml
+//! # the directory the batch command reads the users' inputs from
+//! inputs = "inputs"
+//! # the year of the puzzles the reports link to
+//! year = 2024
+//! # the file holding the adventofcode.com session cookie
+//! session = ".aoc-session"
+//! # the output format, "text" or "json"
+//! format = "text"
+//! # stop solutions after this many seconds
+//! timeout = 30
+//! # keep the answers in this directory, to skip solutions which already ran on the same input
+//! cache = ".aoc-cache"
+//!
+//! # parameters for every part of a day which has them
+//! [days.14]
+//! width = 101
+//! height = 103
+//! ```
+//!
+//! Every setting is optional, relative paths are relative to the file, and flags on the command
+//! line take precedence over it.
+
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+use crate::days::{self, Part};
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub inputs: Option<PathBuf>,
+    pub year: Option<u16>,
+    pub session: Option<PathBuf>,
+    pub format: Option<Format>,
+    timeout: Option<f64>,
+    /// The directory of the answer cache, which is off if there's none.
+    pub cache: Option<PathBuf>,
+    /// Parameter values by day and name.
+    #[serde(default)]
+    days: BTreeMap<String, BTreeMap<String, i64>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Invalid(PathBuf, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "couldn't read {}: {err}", path.display()),
+            Self::Invalid(path, err) => write!(f, "invalid {}: {err}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The config file in `dir` or the closest of its parents.
+pub fn discover(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// The config for the working directory, or the default one if there's no config file.
+pub fn find() -> Result<Config, ConfigError> {
+    let dir = env::current_dir().map_err(|err| ConfigError::Io(PathBuf::from("."), err))?;
+    match discover(&dir) {
+        Some(path) => load(&path),
+        None => Ok(Config::default()),
+    }
+}
+
+pub fn load(path: &Path) -> Result<Config, ConfigError> {
+    let contents = fs::read_to_string(path).map_err(|err| ConfigError::Io(path.into(), err))?;
+    let invalid = |err: String| ConfigError::Invalid(path.into(), err);
+
+    let mut config: Config = toml::from_str(&contents).map_err(|err| invalid(err.to_string()))?;
+    config.check().map_err(invalid)?;
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    for path in [&mut config.inputs, &mut config.session, &mut config.cache]
+        .into_iter()
+        .flatten()
+    {
+        *path = expand_home(path).unwrap_or_else(|| dir.join(&*path));
+    }
+
+    Ok(config)
+}
+
+/// `path` in the home directory if it starts with `~/`.
+fn expand_home(path: &Path) -> Option<PathBuf> {
+    let path = path.strip_prefix("~").ok()?;
+    Some(PathBuf::from(env::var_os("HOME")?).join(path))
+}
+
+impl Config {
+    fn check(&self) -> Result<(), String> {
+        if let Some(timeout) = self.timeout {
+            Duration::try_from_secs_f64(timeout).map_err(|err| format!("timeout: {err}"))?;
+        }
+
+        for (day, values) in &self.days {
+            let day = day
+                .parse()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| format!("{day:?} isn't a day"))?;
+
+            // days which aren't compiled in can't be checked, but they can't run either
+            let Some(day) = days::get(day) else {
+                continue;
+            };
+
+            for (name, value) in values {
+                let parts: Vec<_> = day
+                    .parts
+                    .iter()
+                    .filter(|part| part.params.iter().any(|param| param.name == name))
+                    .collect();
+                if parts.is_empty() {
+                    return Err(format!("day {} has no parameter {name:?}", day.day));
+                }
+
+                for part in parts {
+                    part.params([(name.as_str(), value.to_string().as_str())])
+                        .map_err(|err| format!("day {}: {err}", day.day))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs_f64)
+    }
+
+    /// The values of the parameters `part` of `day` declares, to pass to [Part::params].
+    pub fn param_values(&self, day: u8, part: &Part) -> Vec<(&str, String)> {
+        let Some(values) = self.days.get(&day.to_string()) else {
+            return vec![];
+        };
+
+        values
+            .iter()
+            .filter(|(name, _)| part.params.iter().any(|param| param.name == *name))
+            .map(|(name, value)| (name.as_str(), value.to_string()))
+            .collect()
+    }
+
+    /// The adventofcode.com session cookie, for fetching inputs and answers, if the config says
+    /// where it is.
+    pub fn session_token(&self) -> Option<io::Result<String>> {
+        let path = self.session.as_ref()?;
+        Some(fs::read_to_string(path).map(|token| token.trim().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs, path::PathBuf, time::Duration};
+
+    use super::{discover, load, Config, ConfigError, Format, FILE_NAME};
+
+    /// A fresh directory with a config file, and a directory below it.
+    fn project(name: &str, config: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-config-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/nested")).unwrap();
+        fs::write(dir.join(FILE_NAME), config).unwrap();
+        dir
+    }
+
+    #[test]
+    fn discovery() {
+        let dir = project("discovery", "");
+        assert_eq!(discover(&dir.join("src/nested")), Some(dir.join(FILE_NAME)));
+        assert_eq!(load(&dir.join(FILE_NAME)).unwrap(), Config::default());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn settings() {
+        let dir = project(
+            "settings",
+            "inputs = \"../inputs\"\nyear = 2024\nsession = \"session.txt\"\nformat = \"json\"\n\
+             timeout = 2.5\ncache = \".aoc-cache\"\n",
+        );
+        fs::write(dir.join("session.txt"), "abc123\n").unwrap();
+
+        let config = load(&dir.join(FILE_NAME)).unwrap();
+        assert_eq!(config.inputs, Some(dir.join("../inputs")));
+        assert_eq!(config.year, Some(2024));
+        assert_eq!(config.format, Some(Format::Json));
+        assert_eq!(config.timeout(), Some(Duration::from_millis(2500)));
+        assert_eq!(config.cache, Some(dir.join(".aoc-cache")));
+        assert_eq!(config.session_token().unwrap().unwrap(), "abc123");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "day14")]
+    fn parameters() {
+        let dir = project("parameters", "[days.14]\nwidth = 11\nheight = 7\n");
+        let config = load(&dir.join(FILE_NAME)).unwrap();
+
+        let part = &crate::days::get(14).unwrap().parts[1];
+        assert_eq!(
+            config.param_values(14, part),
+            [("height", "7".to_string()), ("width", "11".to_string())]
+        );
+        assert!(config.param_values(13, part).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn errors() {
+        let mut configs = vec![
+            ("unknown", "colour = \"red\"\n"),
+            ("timeout", "timeout = -1\n"),
+            ("format", "format = \"yaml\"\n"),
+            ("day", "[days.26]\n"),
+        ];
+        if cfg!(feature = "day14") {
+            configs.push(("parameter", "[days.14]\nwdith = 11\n"));
+            configs.push(("range", "[days.14]\nwidth = 1\n"));
+        }
+
+        for (name, contents) in configs {
+            let dir = project(name, contents);
+            let result = load(&dir.join(FILE_NAME));
+            assert!(
+                matches!(result, Err(ConfigError::Invalid(..))),
+                "{contents}: {result:?}"
+            );
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! Compares the answers with another implementation's, e.g. a colleague's binary or script.
+//!
+//! The other solver is a shell command, run once per part with `{day}`, `{part}` and `{input}`
+//! replaced by the day, the part and the path of a file holding the input. Without `{input}` the
+//! input is written to its standard input instead. Its answer is the last line it prints, or the
+//! last lines for answers which are several lines long.
+
+use std::{
+    env,
+    fmt::{self, Display},
+    fs,
+    io::{self, Read, Write},
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answer::Answer,
+    cancel::Failure,
+    days::{Day, Params, Part},
+};
+
+/// How often to check whether the other solver is done.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Another implementation to compare the answers with.
+pub struct Reference {
+    command: String,
+    timeout: Option<Duration>,
+}
+
+/// How the answers of a part compare.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Agree(Answer),
+    Differ {
+        ours: Answer,
+        theirs: String,
+    },
+    /// Our solution failed, so there's nothing to compare with.
+    OursFailed(Failure),
+    TheirsFailed(String),
+}
+
+impl Outcome {
+    /// Whether the solvers disagree, rather than agreeing or there being nothing to compare.
+    pub fn is_disagreement(&self) -> bool {
+        matches!(self, Self::Differ { .. } | Self::TheirsFailed(_))
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Agree(answer) => write!(f, "both answer {answer}"),
+            Self::Differ { ours, theirs } => write!(f, "ours is {ours}, theirs is {theirs}"),
+            Self::OursFailed(failure) => write!(f, "ours {failure}"),
+            Self::TheirsFailed(reason) => write!(f, "theirs failed, {reason}"),
+        }
+    }
+}
+
+/// A file holding an input, which is deleted once dropped.
+struct InputFile(PathBuf);
+
+impl InputFile {
+    fn create(input: &str) -> io::Result<Self> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let name = format!(
+            "aoc-crosscheck-{}-{}.input",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = env::temp_dir().join(name);
+        fs::write(&path, input)?;
+
+        Ok(Self(path))
+    }
+}
+
+impl Drop for InputFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// `text` quoted for `sh`.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Reads all of `from` on its own thread, so that a full pipe doesn't block the child.
+fn drain(mut from: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = vec![];
+        let _ = from.read_to_end(&mut text);
+        String::from_utf8_lossy(&text).into_owned()
+    })
+}
+
+impl Reference {
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            timeout: None,
+        }
+    }
+
+    /// Stops the other solver once it runs for longer than `timeout`.
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Runs the other solver on part `part` of `day`, returning what it printed.
+    pub fn run(&self, day: u8, part: u8, input: &str) -> Result<String, String> {
+        let file = if self.command.contains("{input}") {
+            let file = InputFile::create(input)
+                .map_err(|err| format!("couldn't write the input: {err}"))?;
+            Some(file)
+        } else {
+            None
+        };
+
+        let command = self
+            .command
+            .replace("{day}", &day.to_string())
+            .replace("{part}", &part.to_string())
+            .replace(
+                "{input}",
+                &file
+                    .as_ref()
+                    .map(|file| quote(&file.0.to_string_lossy()))
+                    .unwrap_or_default(),
+            );
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .stdin(if file.is_some() {
+                Stdio::null()
+            } else {
+                Stdio::piped()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("couldn't run {command:?}: {err}"))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let input = input.to_string();
+            // a solver which doesn't read its input closes the pipe early, which isn't an error
+            thread::spawn(move || stdin.write_all(input.as_bytes()));
+        }
+        let stdout = drain(child.stdout.take().expect("stdout is piped"));
+        let stderr = drain(child.stderr.take().expect("stderr is piped"));
+
+        let status = self.wait(&mut child)?;
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+
+        if status.success() {
+            Ok(stdout)
+        } else {
+            let reason = stderr.lines().rev().find(|line| !line.trim().is_empty());
+            Err(match reason {
+                Some(reason) => format!("{status}: {}", reason.trim()),
+                None => status.to_string(),
+            })
+        }
+    }
+
+    fn wait(&self, child: &mut Child) -> Result<std::process::ExitStatus, String> {
+        let start = Instant::now();
+        loop {
+            if let Some(status) = child.try_wait().map_err(|err| err.to_string())? {
+                return Ok(status);
+            }
+
+            if let Some(timeout) = self.timeout.filter(|&timeout| start.elapsed() > timeout) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {timeout:?}"));
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Solves `part` of `day` for `input` both ways, giving up on our solution after `timeout`.
+    pub fn compare(
+        &self,
+        day: &Day,
+        part: &Part,
+        params: &Params,
+        input: &str,
+        timeout: Option<Duration>,
+    ) -> Outcome {
+        let ours = match part.run(Some(input), params, timeout, None) {
+            Ok(run) => run.answer,
+            Err(failure) => return Outcome::OursFailed(failure),
+        };
+
+        let output = match self.run(day.day, part.part, input) {
+            Ok(output) => output,
+            Err(err) => return Outcome::TheirsFailed(err),
+        };
+
+        let ours_text = ours.to_string();
+        let lines: Vec<_> = output
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        let count = ours_text.lines().count().max(1);
+        let theirs = lines[lines.len().saturating_sub(count)..].join("\n");
+
+        if theirs == ours_text {
+            Outcome::Agree(ours)
+        } else {
+            Outcome::Differ { ours, theirs }
+        }
+    }
+
+    /// A smaller input than `input` which the solvers still disagree on, found by leaving out its
+    /// sections and then its lines one at a time, as long as the input stays valid.
+    pub fn shrink(
+        &self,
+        day: &Day,
+        part: &Part,
+        params: &Params,
+        input: &str,
+        timeout: Option<Duration>,
+    ) -> String {
+        let mut input = input.trim().to_string();
+
+        for separator in ["\n\n", "\n"] {
+            let mut index = 0;
+            loop {
+                let pieces: Vec<_> = input.split(separator).collect();
+                if pieces.len() < 2 || index == pieces.len() {
+                    break;
+                }
+
+                let smaller = [&pieces[..index], &pieces[index + 1..]]
+                    .concat()
+                    .join(separator);
+                if (day.validate)(&smaller).is_ok()
+                    && self
+                        .compare(day, part, params, &smaller, timeout)
+                        .is_disagreement()
+                {
+                    input = smaller;
+                } else {
+                    index += 1;
+                }
+            }
+        }
+
+        input
+    }
+}
+
+#[cfg(all(test, unix, feature = "day1"))]
+mod test {
+    use std::time::Duration;
+
+    use super::{Outcome, Reference};
+    use crate::days;
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    fn compare_with(reference: Reference, part: u8) -> Outcome {
+        let day = days::get(1).unwrap();
+        let part = day.part(part).unwrap();
+        let params = part.params([]).unwrap();
+
+        reference.compare(day, part, &params, EXAMPLE, None)
+    }
+
+    fn compare(command: &str, part: u8) -> Outcome {
+        compare_with(
+            Reference::new(command).with_timeout(Some(Duration::from_secs(5))),
+            part,
+        )
+    }
+
+    #[test]
+    fn agreeing() {
+        assert_eq!(compare("echo 11", 1), Outcome::Agree(11.into()));
+        // the answer is the last line, whatever comes before it
+        assert_eq!(
+            compare("echo 'part {part}:'; echo 31", 2),
+            Outcome::Agree(31.into())
+        );
+    }
+
+    #[test]
+    fn differing() {
+        assert_eq!(
+            compare("echo 12", 1),
+            Outcome::Differ {
+                ours: 11.into(),
+                theirs: "12".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn passing_the_input() {
+        // the number of lines of the input, on stdin and in a file
+        let expected = Outcome::Differ {
+            ours: 11.into(),
+            theirs: "6".to_string(),
+        };
+        assert_eq!(compare("wc -l", 1), expected);
+        assert_eq!(compare("wc -l < {input}", 1), expected);
+    }
+
+    #[test]
+    fn shrinking() {
+        // only right when no number on the left is on the right, which takes a single line with
+        // the same number on both sides to get wrong
+        let reference = Reference::new("echo 0");
+        let day = days::get(1).unwrap();
+        let params = day.parts[1].params([]).unwrap();
+
+        let smaller = reference.shrink(day, &day.parts[1], &params, EXAMPLE, None);
+        assert_eq!(smaller, "3   3");
+    }
+
+    #[test]
+    fn failing() {
+        let Outcome::TheirsFailed(reason) = compare("echo oops >&2; exit 3", 1) else {
+            panic!("the other solver failed");
+        };
+        assert!(reason.ends_with("oops"), "{reason}");
+
+        let slow = Reference::new("sleep 5").with_timeout(Some(Duration::from_millis(50)));
+        let Outcome::TheirsFailed(reason) = compare_with(slow, 1) else {
+            panic!("the other solver timed out");
+        };
+        assert!(reason.starts_with("timed out"), "{reason}");
+    }
+}
@@ -0,0 +1,905 @@
+//! A full-screen dashboard to use during the event instead of juggling commands: a table of the
+//! days with the answer and timing of each part on the day's selected input, next to a pane with
+//! the details of the selected day, the log of the runs or a simulation playing.
+//!
+//! Solutions run one at a time on a worker thread, in the order they were asked for, so that they
+//! don't skew each other's timings. The inputs are the bundled ones and the users' in the inputs
+//! directory, see [batch](crate::batch), whose known answers mark the answers right or wrong.
+
+use std::{
+    collections::HashMap,
+    io, panic,
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap},
+    DefaultTerminal, Frame,
+};
+
+use crate::{
+    answer::Answer,
+    batch::User,
+    cache::{self, Cache, Key},
+    config::Config,
+    days::{self, Day, ParamError, Params, Part, Run},
+    progress::{Progress, Tracker},
+    report::format_duration,
+    simulation::Player,
+};
+
+/// How long to wait for a key before redrawing, e.g. to show a solution's progress.
+const TICK: Duration = Duration::from_millis(50);
+
+/// The most lines the log keeps.
+const LOG_LINES: usize = 1000;
+
+/// The most steps a simulation plays per tick.
+const MAX_SPEED: usize = 1 << 12;
+
+/// Where an input comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Input {
+    Bundled,
+    /// The input of the user at this index.
+    User(usize),
+}
+
+/// A part of a day on an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Slot {
+    day: u8,
+    part: u8,
+    input: Input,
+}
+
+#[derive(Debug, Clone)]
+enum Status {
+    Queued(Arc<Tracker>),
+    Running {
+        tracker: Arc<Tracker>,
+        since: Instant,
+    },
+    Solved(Run),
+    Failed(String),
+}
+
+struct Job {
+    slot: Slot,
+    input: Option<String>,
+    params: Params,
+    tracker: Arc<Tracker>,
+}
+
+enum Message {
+    Started(Slot),
+    Finished(Slot, Result<Run, String>),
+}
+
+/// A simulation playing in the pane.
+struct Visualization {
+    day: u8,
+    part: u8,
+    player: Box<dyn Player>,
+    playing: bool,
+    /// The steps played per tick.
+    speed: usize,
+    /// The rows and columns scrolled past, for grids larger than the pane.
+    scroll: (u16, u16),
+}
+
+enum Pane {
+    Details,
+    Log,
+    Visualization(Visualization),
+}
+
+pub struct Dashboard {
+    config: Config,
+    users: Vec<User>,
+    /// The index of the selected day in [days::DAYS].
+    day: usize,
+    /// The index of the selected part.
+    part: usize,
+    /// The selected input of each day, the bundled one if there's none.
+    inputs: HashMap<u8, Input>,
+    statuses: HashMap<Slot, Status>,
+    /// The statistics of each input, or why it's invalid, as they're only gathered once.
+    stats: HashMap<(u8, Input), String>,
+    log: Vec<String>,
+    pane: Pane,
+    jobs: mpsc::Sender<Job>,
+    messages: mpsc::Receiver<Message>,
+    quitting: bool,
+}
+
+/// Runs the jobs until the dashboard is gone.
+fn work(
+    jobs: mpsc::Receiver<Job>,
+    messages: mpsc::Sender<Message>,
+    cache: Option<Cache>,
+    timeout: Option<Duration>,
+) {
+    for job in jobs {
+        let day = days::get(job.slot.day).expect("jobs are only queued for known days");
+        let part = day.part(job.slot.part).expect("and their parts");
+
+        let _ = messages.send(Message::Started(job.slot));
+        let result = cache::run(
+            cache.as_ref(),
+            day,
+            part,
+            job.input.as_deref(),
+            &job.params,
+            timeout,
+            Some(job.tracker),
+        );
+        if messages
+            .send(Message::Finished(
+                job.slot,
+                result.map_err(|failure| failure.to_string()),
+            ))
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+impl Dashboard {
+    /// A dashboard for the days, with the parameters in `config` and the inputs of `users`, which
+    /// takes the answers in `cache` and stops solutions after `timeout`.
+    pub fn new(
+        config: Config,
+        users: Vec<User>,
+        cache: Option<Cache>,
+        timeout: Option<Duration>,
+    ) -> Self {
+        let (jobs, queue) = mpsc::channel();
+        let (done, messages) = mpsc::channel();
+
+        let mut dashboard = Self {
+            config,
+            users,
+            day: 0,
+            part: 0,
+            inputs: HashMap::new(),
+            statuses: HashMap::new(),
+            stats: HashMap::new(),
+            log: vec![],
+            pane: Pane::Details,
+            jobs,
+            messages,
+            quitting: false,
+        };
+        if let Some(cache) = &cache {
+            dashboard.load_cached(cache);
+        }
+
+        thread::spawn(move || work(queue, done, cache, timeout));
+
+        dashboard
+    }
+
+    /// Shows the answers `cache` already has, from earlier runs.
+    fn load_cached(&mut self, cache: &Cache) {
+        let mut count = 0;
+        for day in days::DAYS {
+            for input in self.day_inputs(day) {
+                for part in &day.parts {
+                    let Ok(params) = self.params(day, part) else {
+                        continue;
+                    };
+                    let key = Key::new(day, part, &params, self.input_text(day, input));
+                    if let Some(run) = cache.get(&key) {
+                        let slot = Slot {
+                            day: day.day,
+                            part: part.part,
+                            input,
+                        };
+                        self.statuses.insert(slot, Status::Solved(run));
+                        count += 1;
+                    }
+                }
+            }
+        }
+
+        self.log(format!(
+            "took {count} answers from the cache in {}",
+            cache.dir().display()
+        ));
+    }
+
+    /// Shows the dashboard until it's quit.
+    pub fn run(mut self) -> io::Result<()> {
+        let mut terminal = ratatui::try_init()?;
+
+        // solutions report their panics as failures, printing them would garble the screen
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() == Some("main") {
+                hook(info);
+            }
+        }));
+
+        let result = self.show(&mut terminal);
+        ratatui::restore();
+
+        result
+    }
+
+    fn show(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quitting {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    self.handle(key);
+                }
+            }
+            self.tick();
+        }
+
+        Ok(())
+    }
+
+    fn log(&mut self, line: String) {
+        self.log.push(line);
+        if self.log.len() > LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+
+    fn selected_day(&self) -> Option<&'static Day> {
+        days::DAYS.get(self.day)
+    }
+
+    /// The inputs there are for `day`, the bundled one first.
+    fn day_inputs(&self, day: &Day) -> Vec<Input> {
+        let users = self.users.iter().enumerate();
+        let users = users.filter(|(_, user)| user.inputs.contains_key(&day.day));
+
+        [Input::Bundled]
+            .into_iter()
+            .chain(users.map(|(idx, _)| Input::User(idx)))
+            .collect()
+    }
+
+    fn selected_input(&self, day: &Day) -> Input {
+        self.inputs.get(&day.day).copied().unwrap_or(Input::Bundled)
+    }
+
+    fn input_name(&self, input: Input) -> &str {
+        match input {
+            Input::Bundled => "bundled",
+            Input::User(idx) => &self.users[idx].name,
+        }
+    }
+
+    /// The text of `input`, which is [None] for the bundled one like [Part::run] expects.
+    fn input_text(&self, day: &Day, input: Input) -> Option<&str> {
+        match input {
+            Input::Bundled => None,
+            Input::User(idx) => self.users[idx].inputs.get(&day.day).map(String::as_str),
+        }
+    }
+
+    fn params(&self, day: &Day, part: &Part) -> Result<Params, ParamError> {
+        let values = self.config.param_values(day.day, part);
+        part.params(values.iter().map(|(name, value)| (*name, value.as_str())))
+    }
+
+    /// The answer the user of `input` knows for `part` of `day`, if any.
+    fn expected(&self, day: u8, part: u8, input: Input) -> Option<&Answer> {
+        match input {
+            Input::Bundled => None,
+            Input::User(idx) => self.users[idx].expected(day, part),
+        }
+    }
+
+    /// Queues `part` of `day` on the day's selected input.
+    fn enqueue(&mut self, day: &'static Day, part: &Part) {
+        let input = self.selected_input(day);
+        let slot = Slot {
+            day: day.day,
+            part: part.part,
+            input,
+        };
+        if matches!(
+            self.statuses.get(&slot),
+            Some(Status::Queued(_) | Status::Running { .. })
+        ) {
+            return;
+        }
+
+        let text = self.input_text(day, input);
+        let checked = (day.validate)(text.unwrap_or(day.input))
+            .map_err(|err| format!("invalid input: {err}"))
+            .and_then(|()| self.params(day, part).map_err(|err| err.to_string()));
+        let params = match checked {
+            Ok(params) => params,
+            Err(err) => {
+                self.log(format!("day {}, part {}: {err}", day.day, part.part));
+                self.statuses.insert(slot, Status::Failed(err));
+                return;
+            }
+        };
+
+        let tracker = Arc::new(Tracker::default());
+        let job = Job {
+            slot,
+            input: text.map(str::to_string),
+            params,
+            tracker: tracker.clone(),
+        };
+        if self.jobs.send(job).is_ok() {
+            self.statuses.insert(slot, Status::Queued(tracker));
+        }
+    }
+
+    /// Plays the simulation of the selected part in the pane, if it's one.
+    fn visualize(&mut self) {
+        let Some(day) = self.selected_day() else {
+            return;
+        };
+        let part = &day.parts[self.part];
+        let name = format!("day {}, part {}", day.day, part.part);
+        let input = self.input_text(day, self.selected_input(day));
+        if let Err(err) = (day.validate)(input.unwrap_or(day.input)) {
+            self.log(format!("{name}: invalid input: {err}"));
+            return;
+        }
+        let params = match self.params(day, part) {
+            Ok(params) => params,
+            Err(err) => {
+                self.log(format!("{name}: {err}"));
+                return;
+            }
+        };
+
+        match part.simulation(input, &params) {
+            Some(player) => {
+                self.pane = Pane::Visualization(Visualization {
+                    day: day.day,
+                    part: part.part,
+                    player,
+                    playing: false,
+                    speed: 1,
+                    scroll: (0, 0),
+                });
+            }
+            None => self.log(format!("{name} isn't a simulation")),
+        }
+    }
+
+    fn handle(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if let Pane::Visualization(visualization) = &mut self.pane {
+            if visualization.handle(key) {
+                return;
+            }
+        }
+
+        let last_day = days::DAYS.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('q') => self.quitting = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.quitting = true
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.day = self.day.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.day = (self.day + 1).min(last_day),
+            KeyCode::Home => self.day = 0,
+            KeyCode::End => self.day = last_day,
+            KeyCode::Left => self.part = 0,
+            KeyCode::Right => self.part = 1,
+            KeyCode::Tab => self.part = 1 - self.part,
+            KeyCode::Enter => {
+                if let Some(day) = self.selected_day() {
+                    self.enqueue(day, &day.parts[self.part]);
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(day) = self.selected_day() {
+                    for part in &day.parts {
+                        self.enqueue(day, part);
+                    }
+                }
+            }
+            KeyCode::Char('a') => {
+                for day in days::DAYS {
+                    for part in &day.parts {
+                        self.enqueue(day, part);
+                    }
+                }
+            }
+            KeyCode::Char('i') => {
+                if let Some(day) = self.selected_day() {
+                    let inputs = self.day_inputs(day);
+                    let current = self.selected_input(day);
+                    let idx = inputs.iter().position(|&input| input == current);
+                    let next = inputs[idx.map_or(0, |idx| (idx + 1) % inputs.len())];
+
+                    self.inputs.insert(day.day, next);
+                }
+            }
+            KeyCode::Char('v') => self.visualize(),
+            KeyCode::Char('l') => self.pane = Pane::Log,
+            KeyCode::Char('d') | KeyCode::Esc => self.pane = Pane::Details,
+            _ => {}
+        }
+    }
+
+    /// Takes in the finished solutions and plays the simulation on.
+    fn tick(&mut self) {
+        while let Ok(message) = self.messages.try_recv() {
+            match message {
+                Message::Started(slot) => {
+                    if let Some(Status::Queued(tracker)) = self.statuses.get(&slot) {
+                        let status = Status::Running {
+                            tracker: tracker.clone(),
+                            since: Instant::now(),
+                        };
+                        self.statuses.insert(slot, status);
+                    }
+                }
+                Message::Finished(slot, result) => {
+                    let name = format!(
+                        "day {}, part {}, {} input",
+                        slot.day,
+                        slot.part,
+                        self.input_name(slot.input)
+                    );
+                    let (line, status) = match result {
+                        Ok(run) => {
+                            let check = match self.expected(slot.day, slot.part, slot.input) {
+                                Some(expected) if *expected == run.answer => ", which is right",
+                                Some(_) => ", which is wrong",
+                                None => "",
+                            };
+                            let line = format!(
+                                "{name}: {} in {}{check}",
+                                run.answer.to_string().replace('\n', " "),
+                                format_duration(run.duration)
+                            );
+                            (line, Status::Solved(run))
+                        }
+                        Err(err) => (format!("{name}: {err}"), Status::Failed(err)),
+                    };
+
+                    self.log(line);
+                    self.statuses.insert(slot, status);
+                }
+            }
+        }
+
+        if let Pane::Visualization(visualization) = &mut self.pane {
+            visualization.tick();
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(2)]).areas(frame.area());
+        let [table, pane] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(main);
+
+        self.draw_table(frame, table);
+        match &self.pane {
+            Pane::Details => self.draw_details(frame, pane),
+            Pane::Log => self.draw_log(frame, pane),
+            Pane::Visualization(visualization) => visualization.draw(frame, pane),
+        }
+        self.draw_footer(frame, footer);
+    }
+
+    fn cell(&self, slot: Slot) -> Cell<'static> {
+        let Some(status) = self.statuses.get(&slot) else {
+            return Cell::from("·").dark_gray();
+        };
+
+        match status {
+            Status::Queued(_) => Cell::from("queued").dark_gray(),
+            Status::Running { tracker, since } => {
+                let Progress { phase, done, total } = tracker.progress();
+                let text = match (phase, total) {
+                    ("", _) => format!("running {}", format_duration(since.elapsed())),
+                    (phase, 0) => format!("{phase} {done}"),
+                    (phase, total) => format!("{phase} {}%", done * 100 / total),
+                };
+                Cell::from(text).yellow()
+            }
+            Status::Solved(run) => {
+                let answer = run.answer.to_string();
+                let answer = match answer.split_once('\n') {
+                    Some((first, _)) => format!("{first}…"),
+                    None => answer,
+                };
+                let text = format!("{answer} ({})", format_duration(run.duration));
+
+                match self.expected(slot.day, slot.part, slot.input) {
+                    Some(expected) if *expected == run.answer => {
+                        Cell::from(format!("✓ {text}")).green()
+                    }
+                    Some(_) => Cell::from(format!("✗ {text}")).red(),
+                    None => Cell::from(text),
+                }
+            }
+            Status::Failed(_) => Cell::from("failed").red(),
+        }
+    }
+
+    fn draw_table(&self, frame: &mut Frame, area: Rect) {
+        let rows = days::DAYS.iter().map(|day| {
+            let input = self.selected_input(day);
+            let mut cells = vec![
+                Cell::from(format!("{:>3}", day.day)),
+                Cell::from(day.title),
+                Cell::from(self.input_name(input).to_string()),
+            ];
+            cells.extend(day.parts.iter().map(|part| {
+                self.cell(Slot {
+                    day: day.day,
+                    part: part.part,
+                    input,
+                })
+            }));
+
+            Row::new(cells)
+        });
+
+        let header = Row::new(["Day", "Title", "Input", "Part 1", "Part 2"]).bold();
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::bordered().title(" Advent of Code "))
+            .row_highlight_style(Style::new().add_modifier(Modifier::BOLD))
+            .cell_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+        let mut state = TableState::new()
+            .with_selected_cell((!days::DAYS.is_empty()).then_some((self.day, 3 + self.part)));
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn draw_details(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(day) = self.selected_day() else {
+            let empty = Paragraph::new("no days are enabled").block(Block::bordered());
+            frame.render_widget(empty, area);
+            return;
+        };
+        let input = self.selected_input(day);
+
+        let inputs = self.day_inputs(day);
+        let position = inputs.iter().position(|&other| other == input).unwrap_or(0);
+        let mut lines = vec![
+            Line::from(format!(
+                "input: {} ({} of {})",
+                self.input_name(input),
+                position + 1,
+                inputs.len()
+            )),
+            Line::default(),
+        ];
+
+        let key = (day.day, input);
+        if !self.stats.contains_key(&key) {
+            let text = self.input_text(day, input).unwrap_or(day.input);
+            let stats = match (day.inspect)(text) {
+                Ok(stats) => stats.to_string(),
+                Err(err) => format!("invalid input: {err}"),
+            };
+            self.stats.insert(key, stats);
+        }
+        lines.extend(
+            self.stats[&key]
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+
+        for part in &day.parts {
+            lines.push(Line::default());
+            lines.push(Line::from(format!("part {}", part.part)).bold());
+
+            if let Ok(params) = self.params(day, part) {
+                let params: Vec<_> = params
+                    .iter()
+                    .map(|(name, value)| format!("{name} = {value}"))
+                    .collect();
+                if !params.is_empty() {
+                    lines.push(Line::from(format!("parameters: {}", params.join(", "))));
+                }
+            }
+
+            let slot = Slot {
+                day: day.day,
+                part: part.part,
+                input,
+            };
+            match self.statuses.get(&slot) {
+                Some(Status::Solved(run)) => {
+                    // answers which are pictures start on their own line
+                    let answer = run.answer.to_string();
+                    if answer.contains('\n') {
+                        lines.push(Line::from("answer:"));
+                        lines.extend(answer.lines().map(|line| Line::from(line.to_string())));
+                    } else {
+                        lines.push(Line::from(format!("answer: {answer}")));
+                    }
+                    if let Some(expected) = self.expected(day.day, part.part, input) {
+                        lines.push(Line::from(format!("expected: {expected}")));
+                    }
+                    lines.push(Line::from(format!(
+                        "took {}",
+                        format_duration(run.duration)
+                    )));
+                }
+                Some(Status::Failed(err)) => lines.push(Line::from(err.clone()).red()),
+                Some(Status::Queued(_) | Status::Running { .. }) => {
+                    lines.push(Line::from("running…").yellow())
+                }
+                None => lines.push(Line::from("not run yet").dark_gray()),
+            }
+        }
+
+        let title = format!(" Day {}: {} ", day.day, day.title);
+        let details = Paragraph::new(Text::from(lines))
+            .block(Block::bordered().title(title))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(details, area);
+    }
+
+    fn draw_log(&self, frame: &mut Frame, area: Rect) {
+        // the latest lines which fit, inside the borders
+        let height = usize::from(area.height.saturating_sub(2));
+        let lines = &self.log[self.log.len().saturating_sub(height)..];
+
+        let log = Paragraph::new(lines.join("\n")).block(Block::bordered().title(" Log "));
+        frame.render_widget(log, area);
+    }
+
+    fn draw_footer(&self, frame: &mut Frame, area: Rect) {
+        let keys = match self.pane {
+            Pane::Visualization(_) => {
+                "space play/pause  . , step  + - speed  0 restart  shift+arrows scroll  esc close"
+            }
+            _ => {
+                "↑↓ day  ←→ part  enter run  r run day  a run all  i input  v visualize  l log  \
+                 d details  q quit"
+            }
+        };
+        let latest = self.log.last().map(String::as_str).unwrap_or_default();
+
+        let footer = Paragraph::new(vec![
+            Line::from(latest.to_string()),
+            Line::from(keys).dark_gray(),
+        ]);
+        frame.render_widget(footer, area);
+    }
+}
+
+impl Visualization {
+    /// Handles the keys of the simulation, returning whether `key` was one of them.
+    fn handle(&mut self, key: KeyEvent) -> bool {
+        let (rows, columns) = &mut self.scroll;
+        match (key.code, key.modifiers.contains(KeyModifiers::SHIFT)) {
+            (KeyCode::Char(' '), _) => self.playing = !self.playing,
+            (KeyCode::Char('.'), _) => {
+                self.playing = false;
+                self.player.step();
+            }
+            (KeyCode::Char(','), _) => {
+                self.playing = false;
+                let position = self.player.position();
+                self.player.seek(position.saturating_sub(1));
+            }
+            (KeyCode::Char('+'), _) => self.speed = (self.speed * 2).min(MAX_SPEED),
+            (KeyCode::Char('-'), _) => self.speed = (self.speed / 2).max(1),
+            (KeyCode::Char('0'), _) => {
+                self.player.seek(0);
+            }
+            (KeyCode::Up, true) => *rows = rows.saturating_sub(1),
+            (KeyCode::Down, true) => *rows = rows.saturating_add(1),
+            (KeyCode::Left, true) => *columns = columns.saturating_sub(1),
+            (KeyCode::Right, true) => *columns = columns.saturating_add(1),
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn tick(&mut self) {
+        if !self.playing {
+            return;
+        }
+
+        for _ in 0..self.speed {
+            if !self.player.step() {
+                self.playing = false;
+                return;
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let state = match (self.playing, self.player.is_done()) {
+            (_, true) => "done".to_string(),
+            (true, false) => format!("playing ×{}", self.speed),
+            (false, false) => "paused".to_string(),
+        };
+        let title = format!(
+            " Day {}, part {}: step {}, {state} ",
+            self.day,
+            self.part,
+            self.player.position()
+        );
+
+        let simulation = Paragraph::new(self.player.render())
+            .block(Block::bordered().title(title).border_style(Color::Cyan))
+            .scroll(self.scroll);
+        frame.render_widget(simulation, area);
+    }
+}
+
+#[cfg(all(test, feature = "day1", feature = "day6"))]
+mod test {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        time::{Duration, Instant},
+    };
+
+    use ratatui::{
+        backend::TestBackend,
+        crossterm::event::{KeyCode, KeyEvent},
+        Terminal,
+    };
+
+    use super::{Dashboard, Input, Pane, Slot, Status};
+    use crate::{batch::User, config::Config, days};
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    /// A dashboard with a user whose day 1 input is the example, with a wrong answer for part 2.
+    fn dashboard() -> Dashboard {
+        let user = User {
+            name: "alice".to_string(),
+            inputs: BTreeMap::from([(1, EXAMPLE.to_string())]),
+            answers: HashMap::from([(1, [Some(11.into()), Some(30.into())])]),
+        };
+        Dashboard::new(Config::default(), vec![user], None, None)
+    }
+
+    fn press(dashboard: &mut Dashboard, code: KeyCode) {
+        dashboard.handle(KeyEvent::from(code));
+    }
+
+    fn select(dashboard: &mut Dashboard, day: u8) {
+        press(dashboard, KeyCode::Home);
+        while dashboard.selected_day().unwrap().day != day {
+            press(dashboard, KeyCode::Down);
+        }
+    }
+
+    fn wait(dashboard: &mut Dashboard, slot: Slot) -> Status {
+        let start = Instant::now();
+        loop {
+            dashboard.tick();
+            match dashboard.statuses.get(&slot) {
+                Some(status @ (Status::Solved(_) | Status::Failed(_))) => return status.clone(),
+                _ if start.elapsed() > Duration::from_secs(10) => panic!("{slot:?} never ran"),
+                _ => std::thread::sleep(Duration::from_millis(1)),
+            }
+        }
+    }
+
+    fn screen(dashboard: &mut Dashboard) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|frame| dashboard.draw(frame)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let symbols: Vec<_> = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        symbols
+            .chunks(160)
+            .map(|row| row.concat())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn running() {
+        let mut dashboard = dashboard();
+        select(&mut dashboard, 1);
+        press(&mut dashboard, KeyCode::Enter);
+
+        let slot = Slot {
+            day: 1,
+            part: 1,
+            input: Input::Bundled,
+        };
+        let Status::Solved(run) = wait(&mut dashboard, slot) else {
+            panic!("day 1, part 1 failed");
+        };
+
+        let part = &days::get(1).unwrap().parts[0];
+        let answer = part.solve(None, &part.params([]).unwrap());
+        assert_eq!(run.answer, answer);
+        assert!(screen(&mut dashboard).contains(&answer.to_string()));
+    }
+
+    #[test]
+    fn switching_inputs() {
+        let mut dashboard = dashboard();
+        select(&mut dashboard, 1);
+        press(&mut dashboard, KeyCode::Char('i'));
+        press(&mut dashboard, KeyCode::Char('r'));
+
+        for part in [1, 2] {
+            let slot = Slot {
+                day: 1,
+                part,
+                input: Input::User(0),
+            };
+            wait(&mut dashboard, slot);
+        }
+
+        let screen = screen(&mut dashboard);
+        assert!(screen.contains("alice"), "{screen}");
+        assert!(screen.contains("✓ 11"), "{screen}");
+        assert!(screen.contains("✗ 31"), "{screen}");
+        assert!(dashboard.log.last().unwrap().ends_with("which is wrong"));
+
+        // back to the bundled input, which has no answers yet
+        press(&mut dashboard, KeyCode::Char('i'));
+        assert_eq!(
+            dashboard.selected_input(days::get(1).unwrap()),
+            Input::Bundled
+        );
+    }
+
+    #[test]
+    fn visualizing() {
+        let mut dashboard = dashboard();
+        select(&mut dashboard, 6);
+        press(&mut dashboard, KeyCode::Char('v'));
+
+        let position = |dashboard: &Dashboard| match &dashboard.pane {
+            Pane::Visualization(visualization) => visualization.player.position(),
+            _ => panic!("the simulation isn't showing"),
+        };
+        assert_eq!(position(&dashboard), 0);
+        press(&mut dashboard, KeyCode::Char('.'));
+        press(&mut dashboard, KeyCode::Char('.'));
+        press(&mut dashboard, KeyCode::Char(','));
+        assert_eq!(position(&dashboard), 1);
+
+        press(&mut dashboard, KeyCode::Char(' '));
+        dashboard.tick();
+        assert_eq!(position(&dashboard), 2);
+        assert!(screen(&mut dashboard).contains("step 2, playing ×1"));
+
+        // part 2 isn't a simulation, so the simulation of part 1 stays
+        press(&mut dashboard, KeyCode::Tab);
+        press(&mut dashboard, KeyCode::Char('v'));
+        assert_eq!(position(&dashboard), 2);
+        assert_eq!(
+            dashboard.log.last().unwrap(),
+            "day 6, part 2 isn't a simulation"
+        );
+
+        press(&mut dashboard, KeyCode::Esc);
+        assert!(matches!(dashboard.pane, Pane::Details));
+    }
+}
@@ -1,32 +1,38 @@
 use std::collections::HashMap;
 
-type Output = isize;
+use crate::{
+    answer::Answer,
+    inspect::Stats,
+    parse::{normalize, ParseError, Span},
+};
 
-fn parse_input(input: Option<&str>) -> (Vec<Output>, Vec<Output>) {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day1.input")
-    };
+type Output = isize;
 
-    input
+pub(crate) fn parse(input: &str) -> Result<(Vec<Output>, Vec<Output>), ParseError> {
+    Span::new(input)
         .trim()
         .lines()
-        .map(|line| {
-            line.trim()
-                .split_once(" ")
-                .map(|(a, b)| -> (Output, Output) {
-                    (
-                        a.trim().parse().expect("Failed to parse number"),
-                        b.trim().parse().expect("Failed to parse number"),
-                    )
-                })
-                .expect("Failed to parse line")
-        })
+        .map(|line| line.integers_exact::<Output, 2>().map(|[a, b]| (a, b)))
         .collect()
 }
 
-pub fn part_1(input: Option<&str>) -> Output {
+fn parse_input(input: Option<&str>) -> (Vec<Output>, Vec<Output>) {
+    let input = input.unwrap_or(include_str!("./day1.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let (left, _) = parse(&normalize(input))?;
+    Ok(Stats::new().count("pairs", left.len()))
+}
+
+pub fn part_1(input: Option<&str>) -> Answer {
     let (mut list1, mut list2): (Vec<_>, Vec<_>) = parse_input(input);
 
     list1.sort();
@@ -34,10 +40,11 @@ pub fn part_1(input: Option<&str>) -> Output {
 
     std::iter::zip(list1, list2)
         .map(|(a, b)| (a - b).abs())
-        .sum()
+        .sum::<Output>()
+        .into()
 }
 
-pub fn part_2(input: Option<&str>) -> Output {
+pub fn part_2(input: Option<&str>) -> Answer {
     let (list1, list2): (Vec<_>, Vec<_>) = parse_input(input);
 
     let count_map = list2.into_iter().fold(HashMap::new(), |mut map, x| {
@@ -48,7 +55,8 @@ pub fn part_2(input: Option<&str>) -> Output {
     list1
         .into_iter()
         .map(|x| x * count_map.get(&x).unwrap_or(&0))
-        .sum()
+        .sum::<Output>()
+        .into()
 }
 
 #[cfg(test)]
@@ -77,4 +85,11 @@ mod test {
 
         assert_eq!(result, 31);
     }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example)), 11);
+        assert_eq!(super::part_2(Some(&example)), 31);
+    }
 }
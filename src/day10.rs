@@ -1,79 +1,95 @@
-use std::collections::HashSet;
-
-type Num = u32;
+#[cfg(feature = "image")]
+use crate::image::{Image, TERRAIN};
+use crate::{
+    answer::Answer,
+    inspect::Stats,
+    parse::{normalize, ParseError, Span},
+    search::{
+        grid::{Grid, Pos},
+        reachable, ShortestPaths,
+    },
+};
+
+type Num = u8;
 type Map = Vec<Vec<Num>>;
 
+pub(crate) fn parse(input: &str) -> Result<Map, ParseError> {
+    Span::new(input).digit_grid()
+}
+
 fn parse_input(input: Option<&str>) -> Map {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day10.input")
-    }
-    .trim();
-
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).expect("Invalid input: couldn't parse size") as Num)
-                .collect()
-        })
-        .collect()
+    let input = input.unwrap_or(include_str!("./day10.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
 }
 
-const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
 
-fn find_trail(map: &Map, x: usize, y: usize) -> Box<dyn Iterator<Item = (usize, usize)> + '_> {
-    let current_value = map[x][y];
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let input = normalize(input);
+    parse(&input)?;
+    Ok(Stats::new().grid(&Span::new(&input).char_grid()?))
+}
 
-    if current_value == 9 {
-        return Box::new(std::iter::once((x, y)));
-    }
+/// The map shaded by height, from the trailheads' valleys to the peaks.
+#[cfg(feature = "image")]
+pub fn draw(input: Option<&str>) -> Image {
+    let map = parse_input(input);
 
-    Box::new(
-        DIRECTIONS
-            .iter()
-            .filter_map(move |(dx, dy)| {
-                let x: usize = (x as isize + dx).try_into().ok()?;
-                let y: usize = (y as isize + dy).try_into().ok()?;
-
-                if x >= map.len() || y >= map[x].len() || map[x][y] != current_value + 1 {
-                    None
-                } else {
-                    Some(find_trail(map, x, y))
-                }
-            })
-            .flatten(),
-    )
+    Image::from_fn(map[0].len(), map.len(), |x, y| {
+        TERRAIN.shade(f64::from(map[y][x]) / 9.0)
+    })
 }
 
-pub fn part_1(input: Option<&str>) -> usize {
-    let map = parse_input(input);
+fn climb(grid: Grid<'_, Num>, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+    grid.neighbours_where(pos, |&from, &to| to == from + 1)
+}
 
-    let zeroes = map.iter().enumerate().flat_map(|(i, row)| {
-        row.iter()
-            .enumerate()
-            .filter(|(_, &n)| n == 0)
-            .map(move |(j, _)| (i, j))
-    });
-
-    zeroes
-        .flat_map(|(x, y)| find_trail(&map, x, y).map(move |end| ((x, y), end)))
-        .collect::<HashSet<_>>()
-        .len()
+fn trailheads(grid: Grid<'_, Num>) -> impl Iterator<Item = Pos> + '_ {
+    grid.positions()
+        .filter(move |&pos| grid.get(pos) == Some(&0))
 }
 
-pub fn part_2(input: Option<&str>) -> usize {
-    let map = parse_input(input);
+fn is_peak(grid: Grid<'_, Num>, pos: Pos) -> bool {
+    grid.get(pos) == Some(&9)
+}
 
-    let zeroes = map.iter().enumerate().flat_map(|(i, row)| {
-        row.iter()
-            .enumerate()
-            .filter(|(_, &n)| n == 0)
-            .map(move |(j, _)| (i, j))
-    });
+pub fn part_1(input: Option<&str>) -> Answer {
+    let map = parse_input(input);
+    let grid = Grid::new(&map);
+
+    trailheads(grid)
+        .map(|start| {
+            reachable([start], |&pos| climb(grid, pos))
+                .into_iter()
+                .filter(|&pos| is_peak(grid, pos))
+                .count()
+        })
+        .sum::<usize>()
+        .into()
+}
 
-    zeroes.map(|(x, y)| find_trail(&map, x, y).count()).sum()
+pub fn part_2(input: Option<&str>) -> Answer {
+    let map = parse_input(input);
+    let grid = Grid::new(&map);
+
+    // every step climbs by exactly one, so all trails to a peak are equally short
+    trailheads(grid)
+        .map(|start| {
+            let trails = ShortestPaths::<Pos, u8>::unweighted([start], |&pos| climb(grid, pos));
+
+            trails
+                .costs
+                .keys()
+                .filter(|&&pos| is_peak(grid, pos))
+                .map(|peak| trails.count_paths_to(peak))
+                .sum::<usize>()
+        })
+        .sum::<usize>()
+        .into()
 }
 
 #[cfg(test)]
@@ -106,4 +122,24 @@ mod test {
     fn part_2() {
         assert_eq!(super::part_2(Some(EXAMPLE_2)), 81);
     }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn drawing() {
+        use crate::image::TERRAIN;
+
+        let image = super::draw(Some(EXAMPLE_1));
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.get(0, 0), Some(TERRAIN.shade(0.0)));
+        assert_eq!(image.get(0, 3), Some(TERRAIN.shade(1.0)));
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        use crate::parse::crlf;
+
+        assert_eq!(super::part_1(Some(&crlf(EXAMPLE_1))), 1);
+        assert_eq!(super::part_1(Some(&crlf(EXAMPLE_2))), 36);
+        assert_eq!(super::part_2(Some(&crlf(EXAMPLE_2))), 81);
+    }
 }
@@ -1,28 +1,45 @@
 use std::collections::HashMap;
 
-type Num = u64;
+use crate::{
+    answer::Answer,
+    cancel,
+    inspect::Stats,
+    parse::{normalize, ParseError, Span},
+    progress,
+};
 
-fn parse_input(input: Option<&str>) -> Vec<Num> {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day11.input")
-    }
-    .trim();
+type Num = u64;
 
-    input
+pub(crate) fn parse(input: &str) -> Result<Vec<Num>, ParseError> {
+    Span::new(input)
         .split_whitespace()
-        .map(|x| x.parse().unwrap())
+        .map(|stone| stone.integer())
         .collect()
 }
 
+fn parse_input(input: Option<&str>) -> Vec<Num> {
+    let input = input.unwrap_or(include_str!("./day11.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let stones = parse(&normalize(input))?;
+    Ok(Stats::new().count("stones", stones.len()))
+}
+
 fn blink(el: Num) -> Vec<Num> {
     if el == 0 {
         vec![1]
     } else {
         const TEN: Num = 10;
         let digits = el.ilog10() + 1;
-        if digits % 2 == 0 {
+        if digits.is_multiple_of(2) {
             let base = TEN.pow(digits / 2);
             let start = el / base;
             let end = el % base;
@@ -33,14 +50,18 @@ fn blink(el: Num) -> Vec<Num> {
     }
 }
 
-pub fn part_1(input: Option<&str>, steps: u8) -> usize {
+pub fn part_1(input: Option<&str>, steps: u8) -> Answer {
     let input = parse_input(input);
     let mut map: HashMap<_, usize> = input.into_iter().fold(HashMap::new(), |mut map, el| {
         *map.entry(el).or_insert(0) += 1;
         map
     });
 
+    progress::start("blinking", steps.into());
     for _ in 0..steps {
+        cancel::check();
+        progress::advance();
+
         let mut new_map = HashMap::new();
 
         for (k, v) in map {
@@ -53,7 +74,7 @@ pub fn part_1(input: Option<&str>, steps: u8) -> usize {
         map = new_map;
     }
 
-    map.values().sum()
+    map.values().sum::<usize>().into()
 }
 
 #[cfg(test)]
@@ -67,5 +88,11 @@ mod test {
         assert_eq!(super::part_1(Some(EXAMPLE), 25), 55312);
     }
 
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example), 25), 55312);
+    }
+
     // no test for part_2 as there's no sample output ¯\_(ツ)_/¯
 }
@@ -1,53 +1,76 @@
-use std::{
-    char,
-    collections::{HashMap, HashSet},
+#[cfg(feature = "image")]
+use std::collections::BTreeSet;
+
+#[cfg(feature = "image")]
+use crate::image::{Image, CATEGORIES};
+use crate::{
+    answer::Answer,
+    bitgrid::BitGrid,
+    inspect::Stats,
+    parse::{normalize, ParseError, Span},
+    search::{
+        grid::{Grid, Pos},
+        reachable,
+    },
 };
 
-struct Input {
-    regions: Vec<HashSet<Pos>>,
-    width: usize,
-    height: usize,
+/// A region's cells, in the bounding box of the region whose top left corner is `origin`.
+pub(crate) struct Region {
+    pub(crate) origin: Pos,
+    pub(crate) cells: BitGrid,
 }
 
-fn parse_input(input: Option<&str>) -> Input {
-    let map = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day12.input")
-    }
-    .trim();
-
-    let map: Vec<Vec<_>> = map.lines().map(|x| x.chars().collect()).collect();
-
-    let mut visited = HashSet::new();
+pub(crate) struct Input {
+    pub(crate) regions: Vec<Region>,
+}
 
-    let height = map.len();
-    let width = map[0].len();
+pub(crate) fn parse(input: &str) -> Result<Input, ParseError> {
+    let map = Span::new(input).char_grid()?;
 
-    let points = (0..height).flat_map(|y| (0..width).map(move |x| (x, y)));
+    let grid = Grid::new(&map);
+    let mut visited = BitGrid::new(grid.width(), grid.height());
 
-    let regions: Vec<_> = points
+    let regions: Vec<_> = grid
+        .positions()
         .filter_map(|(x, y)| {
-            if visited.contains(&(x, y)) {
+            if visited.contains(x, y) {
                 None
             } else {
-                let mut region = HashSet::new();
-                get_region(&map, (x, y), &mut region);
+                let region = get_region(grid, (x, y));
 
-                visited.extend(region.iter().copied());
+                let (origin_x, origin_y) = region.origin;
+                for (x, y) in region.cells.iter() {
+                    visited.insert(origin_x + x, origin_y + y);
+                }
 
                 Some(region)
             }
         })
         .collect();
 
-    debug_assert_eq!(regions.iter().flatten().count(), height * width);
+    debug_assert_eq!(visited.count_ones(), grid.height() * grid.width());
 
-    Input {
-        regions,
-        width,
-        height,
-    }
+    Ok(Input { regions })
+}
+
+fn parse_input(input: Option<&str>) -> Input {
+    let input = input.unwrap_or(include_str!("./day12.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let input = normalize(input);
+    let Input { regions } = parse(&input)?;
+
+    Ok(Stats::new()
+        .grid(&Span::new(&input).char_grid()?)
+        .count("regions", regions.len()))
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -67,6 +90,12 @@ impl Direction {
             Self::Right => (1, 0),
         }
     }
+
+    /// The delta along the boundaries facing this direction.
+    fn along(&self) -> (isize, isize) {
+        let (dx, dy) = self.delta();
+        (dy.abs(), dx.abs())
+    }
 }
 
 const DIRECTIONS: [Direction; 4] = [
@@ -76,155 +105,160 @@ const DIRECTIONS: [Direction; 4] = [
     Direction::Right,
 ];
 
-type Pos = (usize, usize);
+fn get_region(grid: Grid<'_, char>, pos: Pos) -> Region {
+    let cells = reachable([pos], |&pos| {
+        grid.neighbours_where(pos, |from, to| from == to)
+    });
+
+    let origin = (
+        cells.iter().map(|&(x, _)| x).min().unwrap_or(0),
+        cells.iter().map(|&(_, y)| y).min().unwrap_or(0),
+    );
+
+    Region {
+        origin,
+        cells: cells
+            .into_iter()
+            .map(|(x, y)| (x - origin.0, y - origin.1))
+            .collect(),
+    }
+}
 
-fn get_region(map: &Vec<Vec<char>>, (x, y): Pos, set: &mut HashSet<Pos>) {
-    let char = map[y][x];
+pub(crate) fn get_region_area(region: &BitGrid) -> usize {
+    region.count_ones()
+}
 
-    set.insert((x, y));
+/// The cells of the region whose neighbour in `dir` is outside of it.
+fn get_region_boundaries(region: &BitGrid, dir: Direction) -> BitGrid {
+    let (dx, dy) = dir.delta();
+    region - &region.shifted(-dx, -dy)
+}
 
+pub fn get_region_perimeter(region: &BitGrid) -> usize {
     DIRECTIONS
         .iter()
-        .map(|dir| dir.delta())
-        .filter_map(|(dx, dy)| {
-            let new_x: usize = ((x as isize) + dx).try_into().ok()?;
-            let new_y: usize = ((y as isize) + dy).try_into().ok()?;
-
-            if new_y < map.len() && new_x < map[new_y].len() && map[new_y][new_x] == char {
-                Some((new_x, new_y))
-            } else {
-                None
-            }
-        })
-        .for_each(|pos| {
-            if !set.contains(&pos) {
-                get_region(map, pos, set);
-            }
-        });
+        .map(|&dir| get_region_boundaries(region, dir).count_ones())
+        .sum()
 }
 
-fn get_region_area(region: &HashSet<Pos>) -> usize {
-    region.len()
+pub(crate) fn get_region_sides(region: &BitGrid) -> usize {
+    DIRECTIONS
+        .iter()
+        .map(|&dir| {
+            // a side starts at each boundary which doesn't continue one right before it
+            let boundaries = get_region_boundaries(region, dir);
+            let (dx, dy) = dir.along();
+            (&boundaries - &boundaries.shifted(dx, dy)).count_ones()
+        })
+        .sum()
 }
 
-fn get_region_boundaries(
-    region: &HashSet<Pos>,
-    width: usize,
-    height: usize,
-) -> impl Iterator<Item = (Pos, Direction)> + use<'_> {
-    region.iter().flat_map(move |&(x, y)| {
-        DIRECTIONS
-            .iter()
-            .filter(move |dir| {
-                let (dx, dy) = dir.delta();
-                let new_x = (x as isize) + dx;
-                let new_y = (y as isize) + dy;
-
-                if new_x < 0 || new_y < 0 {
-                    true
-                } else {
-                    let new_x = new_x as usize;
-                    let new_y = new_y as usize;
-
-                    if new_x >= width || new_y >= height {
-                        true
-                    } else {
-                        !region.contains(&(new_x, new_y))
-                    }
-                }
-            })
-            .map(move |&dir| ((x, y), dir))
-    })
-}
+/// The index of the region of every cell of the garden.
+#[cfg(any(test, feature = "image"))]
+fn owners(regions: &[Region]) -> Vec<Vec<usize>> {
+    let width = regions
+        .iter()
+        .map(|r| r.origin.0 + r.cells.width())
+        .max()
+        .unwrap_or(0);
+    let height = regions
+        .iter()
+        .map(|r| r.origin.1 + r.cells.height())
+        .max()
+        .unwrap_or(0);
+
+    let mut owners = vec![vec![0; width]; height];
+    for (id, Region { origin, cells }) in regions.iter().enumerate() {
+        for (x, y) in cells.iter() {
+            owners[origin.1 + y][origin.0 + x] = id;
+        }
+    }
 
-pub fn get_region_perimeter(region: &HashSet<Pos>, width: usize, height: usize) -> usize {
-    get_region_boundaries(region, width, height).count()
+    owners
 }
 
-fn get_region_sides(region: &HashSet<Pos>, width: usize, height: usize) -> usize {
-    let boundaries_per_alignment: HashMap<_, _> = get_region_boundaries(region, width, height)
-        .map(|((x, y), dir)| {
-            // for boundaries to be aligned, they need have the same direction and the same x (for
-            // left and right boundaries) or y (for up and down boundaries)
-            let key = match dir {
-                Direction::Up => (y, dir),
-                Direction::Down => (y, dir),
-                Direction::Left => (x, dir),
-                Direction::Right => (x, dir),
-            };
-
-            (key, (x, y))
-        })
-        .fold(HashMap::new(), |mut acc, (key, value)| {
-            acc.entry(key).or_insert_with(Vec::new).push(value);
-            acc
-        });
-
-    boundaries_per_alignment
-        .into_iter()
-        .map(|((_, dir), mut boundaries)| -> usize {
-            boundaries.sort_by_key(|&(x, y)| match dir {
-                Direction::Up => x,
-                Direction::Down => x,
-                Direction::Left => y,
-                Direction::Right => y,
-            });
-
-            let discontinuities = boundaries
-                .windows(2)
-                .filter(|window| {
-                    let (x1, y1) = window[0];
-                    let (x2, y2) = window[1];
-
-                    let diff = match dir {
-                        Direction::Up => x2 - x1,
-                        Direction::Down => x2 - x1,
-                        Direction::Left => y2 - y1,
-                        Direction::Right => y2 - y1,
-                    };
+/// The garden with a colour per region, where regions next to each other never share one (unless a
+/// region has more neighbours than there are colours).
+#[cfg(feature = "image")]
+pub fn draw(input: Option<&str>) -> Image {
+    let Input { regions } = parse_input(input);
+    let owners = owners(&regions);
+
+    let mut neighbours = vec![BTreeSet::new(); regions.len()];
+    for (y, row) in owners.iter().enumerate() {
+        for (x, &owner) in row.iter().enumerate() {
+            let right = row.get(x + 1);
+            let below = owners.get(y + 1).map(|row| &row[x]);
+
+            for &other in right.into_iter().chain(below) {
+                if other != owner {
+                    neighbours[owner].insert(other);
+                    neighbours[other].insert(owner);
+                }
+            }
+        }
+    }
 
-                    if diff > 1 {
-                        true
-                    } else {
-                        false
-                    }
-                })
-                .count();
+    // greedily, the first colour none of the neighbours coloured so far has
+    let mut colours: Vec<usize> = Vec::with_capacity(regions.len());
+    for region_neighbours in &neighbours {
+        let taken: BTreeSet<_> = region_neighbours
+            .iter()
+            .filter_map(|&other| colours.get(other))
+            .collect();
+        let colour = (0..CATEGORIES.0.len())
+            .find(|colour| !taken.contains(colour))
+            .unwrap_or(colours.len());
+        colours.push(colour);
+    }
 
-            // there's always at least one side (if it's a single continuous line) + 1 new side per discontinuity
-            1 + discontinuities
-        })
-        .sum()
+    Image::from_fn(owners[0].len(), owners.len(), |x, y| {
+        CATEGORIES.category(colours[owners[y][x]])
+    })
 }
 
-pub fn part_1(input: Option<&str>) -> usize {
-    let Input {
-        height,
-        width,
-        regions,
-    } = parse_input(input);
+pub fn part_1(input: Option<&str>) -> Answer {
+    let Input { regions } = parse_input(input);
 
     regions
         .iter()
-        .map(|region| get_region_area(region) * get_region_perimeter(region, width, height))
-        .sum()
+        .map(|Region { cells, .. }| get_region_area(cells) * get_region_perimeter(cells))
+        .sum::<usize>()
+        .into()
 }
 
-pub fn part_2(input: Option<&str>) -> usize {
-    let Input {
-        height,
-        width,
-        regions,
-    } = parse_input(input);
+pub fn part_2(input: Option<&str>) -> Answer {
+    let Input { regions } = parse_input(input);
 
     regions
         .iter()
-        .map(|region| get_region_area(region) * get_region_sides(region, width, height))
-        .sum()
+        .map(|Region { cells, .. }| get_region_area(cells) * get_region_sides(cells))
+        .sum::<usize>()
+        .into()
 }
 
 #[cfg(test)]
 mod test {
+    use super::{owners, parse_input, Input};
+    use crate::snapshot::assert_snapshot;
+
+    /// Every region drawn with its own symbol, so that regions of the same plant can be told apart.
+    fn render_regions(input: &str) -> String {
+        const SYMBOLS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+        let Input { regions } = parse_input(Some(input));
+
+        owners(&regions)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|id| SYMBOLS[id % SYMBOLS.len()] as char)
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     static EXAMPLE_1: &str = r"
 AAAA
 BBCD
@@ -281,4 +315,51 @@ AAAAAA
         assert_eq!(super::part_2(Some(EXAMPLE_4)), 236);
         assert_eq!(super::part_2(Some(EXAMPLE_5)), 368);
     }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn drawing() {
+        let image = super::draw(Some(EXAMPLE_3));
+        let regions: Vec<Vec<char>> = render_regions(EXAMPLE_3)
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+        assert_eq!((image.width(), image.height()), (10, 10));
+
+        // neighbouring cells have the same colour exactly when they're in the same region
+        for (y, row) in regions.iter().enumerate() {
+            for (x, &region) in row.iter().enumerate() {
+                for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+                    let Some(&neighbour) = regions.get(ny).and_then(|row| row.get(nx)) else {
+                        continue;
+                    };
+
+                    let same_colour = image.get(x, y) == image.get(nx, ny);
+                    assert_eq!(
+                        same_colour,
+                        region == neighbour,
+                        "({x}, {y}) and ({nx}, {ny})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn region_maps() {
+        assert_snapshot!("region_map_example_2", render_regions(EXAMPLE_2));
+        assert_snapshot!("region_map_example_3", render_regions(EXAMPLE_3));
+        assert_snapshot!("region_map_example_5", render_regions(EXAMPLE_5));
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        use crate::parse::crlf;
+
+        assert_eq!(super::part_1(Some(&crlf(EXAMPLE_1))), 140);
+        assert_eq!(super::part_1(Some(&crlf(EXAMPLE_2))), 772);
+        assert_eq!(super::part_1(Some(&crlf(EXAMPLE_3))), 1930);
+        assert_eq!(super::part_2(Some(&crlf(EXAMPLE_4))), 236);
+        assert_eq!(super::part_2(Some(&crlf(EXAMPLE_5))), 368);
+    }
 }
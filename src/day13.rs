@@ -1,12 +1,18 @@
-use nalgebra::{matrix, vector};
-use regex::Regex;
+use crate::{
+    answer::Answer,
+    inspect::Stats,
+    math,
+    parse::{normalize, ParseError, ParseErrorKind, Span},
+};
 
-type Num = isize;
-type F = f64;
+type Num = i128;
 
-struct Pos {
-    x: Num,
-    y: Num,
+const A_COST: Num = 3;
+const B_COST: Num = 1;
+
+pub(crate) struct Pos {
+    pub(crate) x: Num,
+    pub(crate) y: Num,
 }
 
 struct Solution {
@@ -14,107 +20,166 @@ struct Solution {
     b: Num,
 }
 
-struct Machine {
-    button_a: Pos,
-    button_b: Pos,
-    prize: Pos,
+pub(crate) struct Machine {
+    pub(crate) button_a: Pos,
+    pub(crate) button_b: Pos,
+    pub(crate) prize: Pos,
 }
 
-fn parse_button_line(line: &str, button_regex: &Regex) -> Pos {
-    let captures = button_regex.captures(line).expect("Invalid button line");
+/// The position on a `label: X{assign}94, Y{assign}34` line.
+fn parse_pos(line: Span, label: &str, assign: &str) -> Result<Pos, ParseError> {
+    let (name, coordinates) = line.split_once(": ")?;
+    if name.text != label {
+        return Err(name.error(ParseErrorKind::Missing(format!("`{label}`"))));
+    }
 
-    let x = captures["x"].parse().expect("Invalid button x");
-    let y = captures["y"].parse().expect("Invalid button y");
+    let [x, y] = ["X", "Y"].map(|key| coordinates.field_by(key, ", ", assign));
+    Ok(Pos {
+        x: x?.integer()?,
+        y: y?.integer()?,
+    })
+}
 
-    Pos { x, y }
+pub(crate) fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+    Span::new(input)
+        .sections()
+        .map(|machine| {
+            let lines: Vec<_> = machine.lines().collect();
+            let found = lines.len();
+            let [button_a, button_b, prize] = lines.try_into().map_err(|_| {
+                machine.error(ParseErrorKind::WrongItemCount { expected: 3, found })
+            })?;
+
+            Ok(Machine {
+                button_a: parse_pos(button_a, "Button A", "+")?,
+                button_b: parse_pos(button_b, "Button B", "+")?,
+                prize: parse_pos(prize, "Prize", "=")?,
+            })
+        })
+        .collect()
 }
 
 fn parse_machines(input: Option<&str>) -> Vec<Machine> {
-    let button_regex =
-        Regex::new(r"Button .*?: X(?<x>[-+]?\d+), Y(?<y>[-+]?\d+)").expect("Invalid button regex");
-    let prize_regex = Regex::new(r"Prize: X=(?<x>\d+), Y=(?<y>\d+)").expect("Invalid prize regex");
-    let input = input
-        .unwrap_or_else(|| include_str!("./day13.input"))
-        .trim();
-
-    input
-        .split("\n\n")
-        .map(|machine_str| {
-            let [a_str, b_str, prize_str] = machine_str
-                .lines()
-                .collect::<Vec<_>>()
-                .try_into()
-                .expect("expected 3 lines");
-
-            let button_a = parse_button_line(a_str, &button_regex);
-            let button_b = parse_button_line(b_str, &button_regex);
-            let prize = {
-                let captures = prize_regex.captures(prize_str).expect("Invalid prize line");
-
-                let x = captures["x"].parse().expect("Invalid prize x");
-                let y = captures["y"].parse().expect("Invalid prize y");
-
-                Pos { x, y }
-            };
+    let input = input.unwrap_or(include_str!("./day13.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
 
-            Machine {
-                button_a,
-                button_b,
-                prize,
-            }
-        })
-        .collect()
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let machines = parse(&normalize(input))?;
+    Ok(Stats::new().count("machines", machines.len()))
+}
+
+/// The range of `k` for which `base + k * step` isn't negative, as inclusive bounds where `None` is
+/// unbounded.
+fn non_negative_range(base: Num, step: Num) -> (Option<Num>, Option<Num>) {
+    match step.signum() {
+        1 => (Some(-base.div_euclid(step)), None),
+        -1 => (None, Some(base.div_euclid(-step))),
+        _ if base >= 0 => (None, None),
+        _ => (Some(1), Some(0)),
+    }
 }
 
-fn into_approx_int(f: F) -> Option<F> {
-    const EPSILON: F = 1e-4;
+/// The cheapest non-negative presses with `a * a_delta + b * b_delta == target`, for machines whose
+/// buttons move along the same line as the prize.
+fn solve_parallel(a_delta: Num, b_delta: Num, target: Num) -> Option<Solution> {
+    match (a_delta, b_delta) {
+        (0, 0) => (target == 0).then_some(Solution { a: 0, b: 0 }),
+        // pressing a button which doesn't move the claw is a waste
+        (_, 0) => math::exact_div(target, a_delta)
+            .filter(|&a| a >= 0)
+            .map(|a| Solution { a, b: 0 }),
+        (0, _) => math::exact_div(target, b_delta)
+            .filter(|&b| b >= 0)
+            .map(|b| Solution { a: 0, b }),
+        _ => {
+            let (g, x, y) = math::extended_gcd(a_delta, b_delta);
+            let scale = math::exact_div(target, g)?;
+
+            // every solution is (a + k * a_step, b - k * b_step) for some k
+            let (a, b) = (x.checked_mul(scale)?, y.checked_mul(scale)?);
+            let (a_step, b_step) = (b_delta / g, a_delta / g);
+
+            let (a_low, a_high) = non_negative_range(a, a_step);
+            let (b_low, b_high) = non_negative_range(b, -b_step);
+            let low = a_low.max(b_low);
+            let high = match (a_high, b_high) {
+                (Some(a_high), Some(b_high)) => Some(a_high.min(b_high)),
+                (high, None) | (None, high) => high,
+            };
+
+            // the cost changes linearly with k, and can't get below 0, so the cheap end is bounded
+            let slope = A_COST * a_step - B_COST * b_step;
+            let k = if slope >= 0 { low.or(high) } else { high }?;
+            if low.is_some_and(|low| k < low) || high.is_some_and(|high| k > high) {
+                return None;
+            }
 
-    let rounded = f.round();
-    if (f - rounded).abs() < EPSILON {
-        Some(rounded)
-    } else {
-        None
+            Some(Solution {
+                a: a.checked_add(k.checked_mul(a_step)?)?,
+                b: b.checked_sub(k.checked_mul(b_step)?)?,
+            })
+        }
     }
 }
 
 fn solve_machine(machine: &Machine) -> Option<Solution> {
     let Machine {
-        button_a,
-        button_b,
+        button_a: a,
+        button_b: b,
         prize: target,
     } = &machine;
 
-    let solution = matrix![
-        button_a.x as F, button_b.x as F;
-        button_a.y as F, button_b.y as F
-    ]
-    .lu()
-    .solve(&vector![target.x as F, target.y as F])?;
+    let det = math::det(a.x, b.x, a.y, b.y)?;
+    if det == 0 {
+        // the buttons move along the same line, so the prize has to be on it too
+        if math::det(a.x, target.x, a.y, target.y)? != 0
+            || math::det(b.x, target.x, b.y, target.y)? != 0
+        {
+            return None;
+        }
+
+        return if a.x != 0 || b.x != 0 {
+            solve_parallel(a.x, b.x, target.x)
+        } else if target.x == 0 {
+            solve_parallel(a.y, b.y, target.y)
+        } else {
+            None
+        };
+    }
 
-    let a = into_approx_int(solution[0])? as Num;
-    let b = into_approx_int(solution[1])? as Num;
+    // Cramer's rule, which only has a solution if both divisions are exact
+    let presses_a = math::exact_div(math::det(target.x, b.x, target.y, b.y)?, det)?;
+    let presses_b = math::exact_div(math::det(a.x, target.x, a.y, target.y)?, det)?;
 
-    Some(Solution { a, b })
+    (presses_a >= 0 && presses_b >= 0).then_some(Solution {
+        a: presses_a,
+        b: presses_b,
+    })
 }
 
 fn solution_cost(solution: Solution) -> Num {
-    const A_COST: Num = 3;
-    const B_COST: Num = 1;
-
     solution.a * A_COST + solution.b * B_COST
 }
 
-pub fn part_1(input: Option<&str>) -> Num {
+pub fn part_1(input: Option<&str>) -> Answer {
     let machines = parse_machines(input);
 
     machines
         .into_iter()
         .filter_map(|machine| solve_machine(&machine))
         .map(solution_cost)
-        .sum()
+        .sum::<Num>()
+        .into()
 }
 
-pub fn part_2(input: Option<&str>) -> Num {
+pub fn part_2(input: Option<&str>) -> Answer {
     let machines = parse_machines(input);
 
     machines
@@ -127,11 +192,14 @@ pub fn part_2(input: Option<&str>) -> Num {
             solve_machine(&machine)
         })
         .map(solution_cost)
-        .sum()
+        .sum::<Num>()
+        .into()
 }
 
 #[cfg(test)]
 mod test {
+    use super::{solve_machine, Machine, Num, Pos};
+
     static EXAMPLE: &str = r"
 Button A: X+94, Y+34
 Button B: X+22, Y+67
@@ -155,8 +223,61 @@ Prize: X=18641, Y=10279
         assert_eq!(super::part_1(Some(EXAMPLE)), 480);
     }
 
+    #[test]
+    fn malformed_machines() {
+        let error = |input: &str| super::validate(input).unwrap_err().to_string();
+
+        assert_eq!(
+            error("foo 1 bar 2\nbaz 3 4\nqux 5 6"),
+            "line 1, column 1: missing separator \": \""
+        );
+        assert_eq!(
+            error("Button A: X+1, Y+2\nButton C: X+3, Y+4\nPrize: X=5, Y=6"),
+            "line 2, column 1: missing `Button B`"
+        );
+        assert_eq!(
+            error("Button A: X+1, Y+2\nButton B: X+3, Y+4\nPrize: X+5, Y+6"),
+            "line 3, column 8: missing separator \"=\""
+        );
+        assert_eq!(
+            error("Button A: X+1, Y+2\n\nButton B: X+3, Y+4\nPrize: X=5, Y=6"),
+            "line 1, column 1: expected 3 items, found 1"
+        );
+    }
+
+    #[test]
+    fn parallel_buttons() {
+        let machine = |a: (Num, Num), b: (Num, Num), prize: (Num, Num)| Machine {
+            button_a: Pos { x: a.0, y: a.1 },
+            button_b: Pos { x: b.0, y: b.1 },
+            prize: Pos {
+                x: prize.0,
+                y: prize.1,
+            },
+        };
+        let presses =
+            |machine: Machine| solve_machine(&machine).map(|solution| (solution.a, solution.b));
+
+        // B is cheaper, but only A can reach the prize on its own
+        assert_eq!(presses(machine((2, 4), (4, 8), (10, 20))), Some((1, 2)));
+        assert_eq!(presses(machine((2, 4), (4, 8), (12, 24))), Some((0, 3)));
+        // A moves 3 times as far as B, so it costs just as much
+        assert_eq!(presses(machine((3, 3), (1, 1), (7, 7))), Some((0, 7)));
+        assert_eq!(presses(machine((2, 4), (4, 8), (11, 22))), None);
+        assert_eq!(presses(machine((2, 4), (4, 8), (10, 21))), None);
+        assert_eq!(presses(machine((0, 2), (0, 3), (0, 7))), Some((2, 1)));
+        assert_eq!(presses(machine((0, 0), (0, 0), (1, 0))), None);
+        assert_eq!(presses(machine((2, 2), (-1, -1), (3, 3))), Some((2, 1)));
+    }
+
     #[test]
     fn part_2() {
-        assert_eq!(super::part_2(None), 93209116744825);
+        assert_eq!(super::part_2(None), 93209116744825i128);
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example)), 480);
     }
 }
@@ -1,16 +1,28 @@
 use std::{
     collections::HashMap,
-    num::ParseIntError,
+    fmt::{self, Display},
     ops::{Add, Mul},
-    str::FromStr,
+};
+
+#[cfg(feature = "image")]
+use crate::image::{Image, HEAT};
+use crate::{
+    answer::Answer,
+    bitgrid::BitGrid,
+    cancel,
+    inspect::Stats,
+    math,
+    parse::{normalize, ParseError, Span},
+    progress,
+    simulation::Simulation,
 };
 
 type Num = isize;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Vec2 {
-    x: Num,
-    y: Num,
+pub(crate) struct Vec2 {
+    pub(crate) x: Num,
+    pub(crate) y: Num,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -84,77 +96,92 @@ impl Mul<Num> for Vec2 {
     }
 }
 
-impl FromStr for Vec2 {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let [x, y] = s
-            .split(",")
-            .map(|part| part.parse())
-            .collect::<Result<Vec<_>, _>>()?
-            .try_into()
-            .unwrap();
-
-        Ok(Self { x, y })
-    }
-}
-
 #[derive(Debug, Clone)]
-struct Robot {
-    pos: Vec2,
-    vel: Vec2,
+pub(crate) struct Robot {
+    pub(crate) pos: Vec2,
+    pub(crate) vel: Vec2,
 }
 
-fn parse_input(input: Option<&str>) -> Vec<Robot> {
-    let input = input.unwrap_or_else(|| include_str!("day14.input")).trim();
+fn parse_vec2(span: Span) -> Result<Vec2, ParseError> {
+    let [x, y] = span.integers_exact()?;
+    Ok(Vec2 { x, y })
+}
 
-    input
+pub(crate) fn parse(input: &str) -> Result<Vec<Robot>, ParseError> {
+    Span::new(input)
+        .trim()
         .lines()
         .map(|line| {
-            let [pos, vel] = line
-                .split(" ")
-                .map(|part| part.split("=").last().unwrap().parse().unwrap())
-                .collect::<Vec<Vec2>>()
-                .try_into()
-                .unwrap();
-
-            Robot { pos, vel }
+            Ok(Robot {
+                pos: parse_vec2(line.field("p")?)?,
+                vel: parse_vec2(line.field("v")?)?,
+            })
         })
         .collect()
 }
 
-fn print_map(robots: &Vec<Robot>, size: &Vec2) {
-    for y in 0..size.y {
-        for x in 0..size.x {
-            let pos = Vec2 { x, y };
-            let robot_count = robots.iter().filter(|r| r.pos == pos).count();
+fn parse_input(input: Option<&str>) -> Vec<Robot> {
+    let input = input.unwrap_or(include_str!("day14.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// The robots of `input` in a room of `size`. Robots starting outside of it wrap around its edges,
+/// like they do when they move, so that a smaller room than the input's still has them all inside.
+fn parse_room(input: Option<&str>, size: (Num, Num)) -> (Vec<Robot>, Vec2) {
+    let size = Vec2 {
+        x: size.0,
+        y: size.1,
+    };
+    let mut robots = parse_input(input);
+    for robot in &mut robots {
+        robot.pos.normalize(&size);
+    }
+
+    (robots, size)
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
 
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let robots = parse(&normalize(input))?;
+    Ok(Stats::new().count("robots", robots.len()))
+}
+
+fn render_map(robots: &[Robot], size: &Vec2) -> String {
+    let mut counts = vec![0; (size.x * size.y) as usize];
+    for robot in robots {
+        counts[(robot.pos.y * size.x + robot.pos.x) as usize] += 1;
+    }
+
+    let mut map = String::new();
+    for row in counts.chunks(size.x as usize) {
+        for &robot_count in row {
             match robot_count {
-                0 => print!("."),
-                x => print!("{}", x),
+                0 => map.push('.'),
+                x => map += &x.to_string(),
             };
         }
 
-        println!();
+        map.push('\n');
     }
+
+    map
 }
 
-const ITERATIONS: usize = 100;
-pub fn part_1(input: Option<&str>, size: (Num, Num)) -> Num {
-    let mut robots = parse_input(input);
-    let size = Vec2 {
-        x: size.0,
-        y: size.1,
-    };
+/// The seconds part 1 waits for.
+pub const ITERATIONS: usize = 100;
+pub fn part_1(input: Option<&str>, size: (Num, Num)) -> Answer {
+    let (mut robots, size) = parse_room(input, size);
 
     for robot in &mut robots {
         robot.pos = robot.pos + robot.vel * ITERATIONS as Num;
         robot.pos.normalize(&size);
     }
 
-    #[cfg(debug_assertions)]
-    print_map(&robots, &size);
-
     let robots_per_quadrant = robots.into_iter().fold(
         HashMap::from([
             (Quadrant::TopLeft, 0),
@@ -171,63 +198,185 @@ pub fn part_1(input: Option<&str>, size: (Num, Num)) -> Num {
         },
     );
 
-    robots_per_quadrant.values().product()
+    robots_per_quadrant.values().product::<Num>().into()
 }
 
-fn has_square_of_size(robots: &Vec<Robot>, board_size: &Vec2, square_size: Num) -> bool {
-    let square = (0..square_size)
-        .flat_map(|y| {
-            (0..square_size).map(move |x| Vec2 {
-                x: x as Num,
-                y: y as Num,
-            })
-        })
-        .collect::<Vec<_>>();
+fn occupied(robots: &[Robot], size: &Vec2) -> BitGrid {
+    let mut grid = BitGrid::new(size.x as usize, size.y as usize);
+    for robot in robots {
+        grid.insert(robot.pos.x as usize, robot.pos.y as usize);
+    }
+    grid
+}
 
-    for x in 0..(board_size.x - square_size as Num) {
-        for y in 0..(board_size.y - square_size as Num) {
-            let base_pos = Vec2 { x, y };
+fn has_square_of_size(robots: &[Robot], board_size: &Vec2, square_size: Num) -> bool {
+    let occupied = occupied(robots, board_size);
 
-            if square.iter().all(|pos| {
-                let checked_pos = base_pos + *pos;
-                robots.iter().any(|robot| robot.pos == checked_pos)
-            }) {
-                return true;
-            }
-        }
+    // the top left corners of square_size occupied cells in a row, then of square_size such rows
+    let rows = (1..square_size).fold(occupied.clone(), |rows, x| &rows & &occupied.shifted(-x, 0));
+    let squares = (1..square_size).fold(rows.clone(), |squares, y| &squares & &rows.shifted(0, -y));
+
+    !squares.is_empty()
+}
+
+/// How spread out `positions` are, as `n` times their variance, which stays an integer.
+fn spread(positions: impl Iterator<Item = Num>) -> i128 {
+    let (mut count, mut sum, mut sum_of_squares) = (0i128, 0i128, 0i128);
+    for position in positions {
+        let position = position as i128;
+        count += 1;
+        sum += position;
+        sum_of_squares += position * position;
     }
 
-    false
+    count * sum_of_squares - sum * sum
 }
 
-pub fn part_2(input: Option<&str>, size: (Num, Num)) -> Num {
-    let mut robots = parse_input(input);
-    let size = Vec2 {
-        x: size.0,
-        y: size.1,
-    };
+/// The time in `0..period` at which the robots' coordinates on one axis are the least spread out.
+fn tightest_time(robots: &[Robot], period: Num, axis: impl Fn(&Vec2) -> Num) -> Num {
+    (0..period)
+        .min_by_key(|&seconds| {
+            spread(
+                robots.iter().map(|robot| {
+                    (axis(&robot.pos) + axis(&robot.vel) * seconds).rem_euclid(period)
+                }),
+            )
+        })
+        .unwrap_or(0)
+}
 
-    let mut seconds = 0;
-    loop {
-        for robot in &mut robots {
+fn robots_after(robots: &[Robot], size: &Vec2, seconds: Num) -> Vec<Robot> {
+    robots
+        .iter()
+        .map(|robot| {
+            let mut pos = robot.pos + robot.vel * seconds;
+            pos.normalize(size);
+            Robot { pos, ..*robot }
+        })
+        .collect()
+}
+
+/// The robots moving around the room, one second per step. They never stop, so it's never done.
+pub struct Room {
+    start: Vec<Robot>,
+    robots: Vec<Robot>,
+    size: Vec2,
+    seconds: Num,
+}
+
+impl Display for Room {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", render_map(&self.robots, &self.size))
+    }
+}
+
+impl Simulation for Room {
+    /// The seconds since the start, as the positions follow from them.
+    type Snapshot = Num;
+
+    fn step(&mut self) {
+        for robot in &mut self.robots {
             robot.pos = robot.pos + robot.vel;
-            robot.pos.normalize(&size);
+            robot.pos.normalize(&self.size);
         }
 
-        seconds += 1;
+        self.seconds += 1;
+    }
 
-        if has_square_of_size(&robots, &size, 3) {
-            break;
-        }
+    fn is_done(&self) -> bool {
+        false
+    }
+
+    fn snapshot(&self) -> Num {
+        self.seconds
     }
 
-    print_map(&robots, &size);
+    fn restore(&mut self, &seconds: &Num) {
+        self.robots = robots_after(&self.start, &self.size, seconds);
+        self.seconds = seconds;
+    }
+}
+
+/// The room before the robots move.
+pub fn room(input: Option<&str>, size: (Num, Num)) -> Room {
+    let (robots, size) = parse_room(input, size);
+
+    Room {
+        start: robots.clone(),
+        robots,
+        size,
+        seconds: 0,
+    }
+}
+
+/// The first second at which the robots draw the picture, if they ever do.
+fn picture_time(robots: &[Robot], size: &Vec2) -> Option<Num> {
+    // The x coordinates repeat every width seconds and the y coordinates every height seconds. The
+    // picture packs the robots together on both axes at once, so the time at which each axis is the
+    // tightest pins it down with the chinese remainder theorem.
+    let period = math::lcm(size.x as i128, size.y as i128).expect("the room is small") as Num;
+    let tightest = math::crt(&[
+        (
+            tightest_time(robots, size.x, |v| v.x) as i128,
+            size.x as i128,
+        ),
+        (
+            tightest_time(robots, size.y, |v| v.y) as i128,
+            size.y as i128,
+        ),
+    ])
+    .map(|(seconds, _)| seconds as Num)
+    // the robots don't move before the first second
+    .map(|seconds| if seconds == 0 { period } else { seconds });
+
+    tightest
+        .filter(|&seconds| has_square_of_size(&robots_after(robots, size, seconds), size, 3))
+        .or_else(|| {
+            // the robots are back where they started after at most period seconds
+            progress::start("waiting for the picture", period as u64);
+            (1..=period).find(|&seconds| {
+                cancel::check();
+                progress::advance();
+                has_square_of_size(&robots_after(robots, size, seconds), size, 3)
+            })
+        })
+}
+
+/// The room after `seconds`, or once the robots draw the picture if there's no `seconds`, shaded
+/// by the number of robots on each tile. [None] if there are no `seconds` and the robots never draw
+/// the picture.
+#[cfg(feature = "image")]
+pub fn draw(input: Option<&str>, size: (Num, Num), seconds: Option<Num>) -> Option<Image> {
+    let (robots, size) = parse_room(input, size);
+    let seconds = match seconds {
+        Some(seconds) => seconds,
+        None => picture_time(&robots, &size)?,
+    };
+
+    let mut counts = vec![0u32; (size.x * size.y) as usize];
+    for robot in robots_after(&robots, &size, seconds) {
+        counts[(robot.pos.y * size.x + robot.pos.x) as usize] += 1;
+    }
+    let most = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    let image = Image::from_fn(size.x as usize, size.y as usize, |x, y| {
+        let count = counts[y * size.x as usize + x];
+        HEAT.shade(f64::from(count) / f64::from(most))
+    });
+    Some(image)
+}
 
-    seconds
+pub fn part_2(input: Option<&str>, size: (Num, Num)) -> Answer {
+    let (robots, size) = parse_room(input, size);
+    let seconds = picture_time(&robots, &size).expect("the robots never draw a picture");
+    seconds.into()
 }
 
 #[cfg(test)]
 mod test {
+    use super::{parse_input, render_map, robots_after, Vec2};
+    use crate::{simulation::Simulation, snapshot::assert_snapshot};
+
     static EXAMPLE: &str = r"
 p=0,4 v=3,-3
 p=6,3 v=-1,-3
@@ -243,8 +392,95 @@ p=2,4 v=2,-3
 p=9,5 v=-3,-3
 ";
 
+    const SIZE: Vec2 = Vec2 { x: 11, y: 7 };
+
     #[test]
     fn part_1() {
         assert_eq!(super::part_1(Some(EXAMPLE), (11, 7)), 12);
     }
+
+    #[test]
+    fn part_2() {
+        // nine robots with different velocities, which meet in a 3×3 square after 30 seconds
+        let robots: Vec<_> = (0..9)
+            .map(|i| {
+                let vel = Vec2 {
+                    x: i % 3 - 1 + i,
+                    y: i / 3 - 1 + 2 * i,
+                };
+                let mut pos = Vec2 {
+                    x: 4 + i % 3,
+                    y: 2 + i / 3,
+                } + vel * -30;
+                pos.normalize(&SIZE);
+                format!("p={},{} v={},{}", pos.x, pos.y, vel.x, vel.y)
+            })
+            .collect();
+
+        assert_eq!(super::part_2(Some(&robots.join("\n")), (11, 7)), 30);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn drawing() {
+        use crate::image::HEAT;
+
+        // the two robots at 6,0 after 100 seconds are the most crowded tile
+        let image = super::draw(Some(EXAMPLE), (11, 7), Some(100)).unwrap();
+        assert_eq!((image.width(), image.height()), (11, 7));
+        assert_eq!(image.get(6, 0), Some(HEAT.shade(1.0)));
+        assert_eq!(image.get(9, 0), Some(HEAT.shade(0.5)));
+        assert_eq!(image.get(0, 0), Some(HEAT.shade(0.0)));
+
+        // the example's robots never draw the picture
+        assert_eq!(super::draw(Some(EXAMPLE), (11, 7), None), None);
+    }
+
+    #[test]
+    fn robots_after_100_seconds() {
+        let robots = robots_after(&parse_input(Some(EXAMPLE)), &SIZE, 100);
+
+        assert_snapshot!("robots_after_100_seconds", render_map(&robots, &SIZE));
+    }
+
+    #[test]
+    fn single_robot_frames() {
+        let mut room = super::room(Some("p=2,4 v=2,-3"), (SIZE.x, SIZE.y));
+
+        let mut frames = vec![];
+        for second in 0..=5 {
+            frames.push(format!("After {second} seconds:\n{room}"));
+            room.step();
+        }
+
+        assert_snapshot!("single_robot_frames", frames.join("\n"));
+
+        room.restore(&2);
+        assert!(frames[2].ends_with(&room.to_string()));
+    }
+
+    #[test]
+    fn robots_outside_of_the_room() {
+        // the same robots as the example's, but some of them a room or two further
+        let robots = "p=11,4 v=3,-3\np=-5,3 v=-1,-3\np=10,10 v=-1,2\n".to_string()
+            + &EXAMPLE
+                .trim()
+                .lines()
+                .skip(3)
+                .collect::<Vec<_>>()
+                .join("\n");
+        assert_eq!(super::part_1(Some(&robots), (11, 7)), 12);
+
+        let room = super::room(Some(&robots), (SIZE.x, SIZE.y));
+        assert_eq!(
+            room.to_string(),
+            super::room(Some(EXAMPLE), (SIZE.x, SIZE.y)).to_string()
+        );
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example), (11, 7)), 12);
+    }
 }
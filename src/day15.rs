@@ -1,14 +1,23 @@
 use std::{
     collections::{HashMap, HashSet},
+    fmt::{self, Display},
     ops::Add,
 };
 
+use crate::{
+    answer::Answer,
+    cancel,
+    inspect::Stats,
+    parse::{normalize, ParseError, ParseErrorKind, Span},
+    simulation::Simulation,
+};
+
 type Num = isize;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Vec2 {
-    x: Num,
-    y: Num,
+    pub(crate) x: Num,
+    pub(crate) y: Num,
 }
 
 impl Vec2 {
@@ -52,11 +61,11 @@ pub enum Item {
     Robot,
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Map(HashMap<Vec2, Item>, Vec2);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map(pub(crate) HashMap<Vec2, Item>, pub(crate) Vec2);
 
 impl Map {
-    fn to_wide_map(self) -> Self {
+    fn into_wide_map(self) -> Self {
         let mut map = HashMap::new();
 
         for (Vec2 { x, y }, item) in self.0 {
@@ -160,7 +169,7 @@ impl Map {
             .collect();
 
         if moved.iter().all(|item| item.is_some()) {
-            let positions_to_move = moved.into_iter().map(|item| item.expect("Unreachable: found None in `moved` even though .all(|item| item.is_some()) is true")).flatten().chain(std::iter::once(from_pos)).collect();
+            let positions_to_move = moved.into_iter().flat_map(|item| item.expect("Unreachable: found None in `moved` even though .all(|item| item.is_some()) is true")).chain(std::iter::once(from_pos)).collect();
 
             Some(positions_to_move)
         } else {
@@ -169,10 +178,8 @@ impl Map {
     }
 }
 
-impl ToString for Map {
-    fn to_string(&self) -> String {
-        let mut result = String::new();
-
+impl Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for y in 0..self.1.y {
             for x in 0..self.1.x {
                 let pos = Vec2 { x, y };
@@ -192,14 +199,13 @@ impl ToString for Map {
                     None => '.',
                 };
 
-                result.push(item);
+                write!(f, "{item}")?;
             }
 
-            result.push('\n');
+            writeln!(f)?;
         }
 
-        // result.trim().to_string()
-        result
+        Ok(())
     }
 }
 
@@ -212,117 +218,216 @@ pub enum Direction {
 }
 
 #[derive(Debug)]
-struct Input {
-    map: Map,
-    directions: Vec<Direction>,
+pub(crate) struct Input {
+    pub(crate) map: Map,
+    pub(crate) directions: Vec<Direction>,
 }
 
-fn parse_map(input: &str) -> Map {
-    Map(
-        input
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars().enumerate().filter_map(move |(x, c)| {
-                    let pos = Vec2 {
-                        x: x as Num,
-                        y: y as Num,
-                    };
-
-                    let item = match c {
-                        '#' => Item::Wall,
-                        'O' => Item::Block,
-                        '@' => Item::Robot,
-                        '.' => return None,
-                        _ => panic!("Invalid item"),
-                    };
-
-                    Some((pos, item))
-                })
-            })
-            .collect(),
+fn parse_map(span: Span) -> Result<Map, ParseError> {
+    let grid = span.char_grid()?;
+    let mut items = HashMap::new();
+    let mut has_robot = false;
+
+    for (y, line) in span.trim().lines().enumerate() {
+        for (x, (c_span, c)) in line.chars().enumerate() {
+            let pos = Vec2 {
+                x: x as Num,
+                y: y as Num,
+            };
+
+            let item = match c {
+                '#' => Item::Wall,
+                'O' => Item::Block,
+                '@' if !has_robot => {
+                    has_robot = true;
+                    Item::Robot
+                }
+                '.' => continue,
+                _ => return Err(c_span.error(ParseErrorKind::UnexpectedChar(c))),
+            };
+
+            items.insert(pos, item);
+        }
+    }
+
+    if !has_robot {
+        return Err(span.error(ParseErrorKind::Missing("robot".to_string())));
+    }
+
+    Ok(Map(
+        items,
         Vec2 {
-            x: input.lines().next().unwrap().len() as Num,
-            y: input.lines().count() as Num,
+            x: grid[0].len() as Num,
+            y: grid.len() as Num,
         },
-    )
+    ))
 }
 
-fn parse_input(input: Option<&str>) -> Input {
-    let input = input.unwrap_or_else(|| include_str!("day15.input")).trim();
-
-    let (map_str, directions_str) = input.split_once("\n\n").expect("Invalid input");
+pub(crate) fn parse(input: &str) -> Result<Input, ParseError> {
+    let [map, directions] = Span::new(input).sections_exact()?;
 
-    let map: Map = parse_map(map_str);
+    let map = parse_map(map)?;
 
-    let directions = directions_str
-        .split("\n")
+    let directions = directions
+        .lines()
         .flat_map(|line| line.chars())
-        .map(|c| match c {
-            '^' => Direction::Up,
-            'v' => Direction::Down,
-            '<' => Direction::Left,
-            '>' => Direction::Right,
-            _ => panic!("Invalid direction"),
+        .map(|(span, c)| match c {
+            '^' => Ok(Direction::Up),
+            'v' => Ok(Direction::Down),
+            '<' => Ok(Direction::Left),
+            '>' => Ok(Direction::Right),
+            _ => Err(span.error(ParseErrorKind::UnexpectedChar(c))),
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    Input { map, directions }
+    Ok(Input { map, directions })
 }
 
-fn move_robot(map: &mut Map, directions: &Vec<Direction>) -> Vec2 {
-    let mut robot_pos = map
-        .0
-        .iter()
-        .find_map(|(pos, item)| {
-            if item == &Item::Robot {
-                Some(*pos)
-            } else {
-                None
-            }
-        })
-        .expect("Invalid map: no robot");
+fn parse_input(input: Option<&str>) -> Input {
+    let input = input.unwrap_or(include_str!("day15.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
 
-    for &direction in directions.into_iter() {
-        if let Some(new_pos) = map.move_item_at(robot_pos, direction) {
-            robot_pos = new_pos;
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let input = normalize(input);
+    let Input { directions, .. } = parse(&input)?;
+    let [map, _] = Span::new(&input).sections_exact()?;
+
+    Ok(Stats::new()
+        .grid(&map.char_grid()?)
+        .count("moves", directions.len()))
+}
+
+/// The robot following its directions, one move per step.
+pub struct Warehouse {
+    map: Map,
+    robot: Vec2,
+    directions: Vec<Direction>,
+    moves: usize,
+}
+
+impl Warehouse {
+    fn new(map: Map, directions: Vec<Direction>) -> Self {
+        let robot = map
+            .0
+            .iter()
+            .find_map(|(pos, item)| {
+                if item == &Item::Robot {
+                    Some(*pos)
+                } else {
+                    None
+                }
+            })
+            .expect("Invalid map: no robot");
+
+        Self {
+            map,
+            robot,
+            directions,
+            moves: 0,
         }
     }
 
-    robot_pos
+    /// The map once the robot made every move.
+    fn finish(mut self) -> Map {
+        while !self.is_done() {
+            cancel::check();
+            self.step();
+        }
+
+        self.map
+    }
 }
 
-pub fn part_1(input: Option<&str>) -> Num {
-    let Input {
-        mut map,
-        directions,
-    } = parse_input(input);
+impl Display for Warehouse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
 
-    move_robot(&mut map, &directions);
+impl Simulation for Warehouse {
+    type Snapshot = (Map, Vec2, usize);
+
+    fn step(&mut self) {
+        let direction = self.directions[self.moves];
+        if let Some(new_pos) = self.map.move_item_at(self.robot, direction) {
+            self.robot = new_pos;
+        }
+
+        self.moves += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.moves == self.directions.len()
+    }
+
+    fn snapshot(&self) -> Self::Snapshot {
+        (self.map.clone(), self.robot, self.moves)
+    }
+
+    fn restore(&mut self, (map, robot, moves): &Self::Snapshot) {
+        self.map = map.clone();
+        self.robot = *robot;
+        self.moves = *moves;
+    }
+}
+
+/// The warehouse of part 1, or the wide one of part 2, before the robot moves.
+pub fn warehouse(input: Option<&str>, wide: bool) -> Warehouse {
+    let Input { map, directions } = parse_input(input);
+    let map = if wide { map.into_wide_map() } else { map };
+
+    Warehouse::new(map, directions)
+}
+
+pub fn part_1(input: Option<&str>) -> Answer {
+    let map = warehouse(input, false).finish();
 
     map.0
         .iter()
         .filter(|(_, item)| item == &&Item::Block)
         .map(|(pos, _)| pos.get_coordinates())
-        .sum()
+        .sum::<Num>()
+        .into()
 }
 
-pub fn part_2(input: Option<&str>) -> Num {
-    let Input { map, directions } = parse_input(input);
-
-    let mut map = map.to_wide_map();
-
-    move_robot(&mut map, &directions);
+pub fn part_2(input: Option<&str>) -> Answer {
+    let map = warehouse(input, true).finish();
 
     map.0
         .iter()
         .filter(|(_, item)| item == &&Item::Block || item == &&Item::WideBlock)
         .map(|(pos, _)| pos.get_coordinates())
-        .sum()
+        .sum::<Num>()
+        .into()
 }
 
 #[cfg(test)]
 mod test {
+    use crate::{
+        simulation::{Player, Replay, Simulation},
+        snapshot::assert_snapshot,
+    };
+
+    static SMALL_EXAMPLE: &str = r"
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+";
+
     static EXAMPLE: &str = r"
 ##########
 #..O..O.O#
@@ -349,66 +454,76 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
 
     #[test]
     fn part_1() {
-        let super::Input {
-            mut map,
-            directions,
-        } = super::parse_input(Some(EXAMPLE));
+        let map = super::warehouse(Some(EXAMPLE), false).finish();
+
+        assert_snapshot!("map_after_moves", map);
+
+        assert_eq!(super::part_1(Some(EXAMPLE)), 10092);
+    }
 
-        super::move_robot(&mut map, &directions);
+    #[test]
+    fn inspecting() {
+        use crate::inspect::Value;
 
+        let stats = super::inspect(EXAMPLE).unwrap();
         assert_eq!(
-            map,
-            super::parse_map(
-                r"
-##########
-#.O.O.OOO#
-#........#
-#OO......#
-#OO@.....#
-#O#.....O#
-#O.....OO#
-#O.....OO#
-#OO....OO#
-##########
-"
-                .trim()
-            )
+            stats.get("size"),
+            Some(&Value::Size {
+                width: 10,
+                height: 10
+            })
         );
+        assert_eq!(stats.get("moves"), Some(&Value::Count(700)));
+    }
 
-        assert_eq!(super::part_1(Some(EXAMPLE)), 10092);
+    #[test]
+    fn small_example_moves() {
+        let mut warehouse = super::warehouse(Some(SMALL_EXAMPLE), false);
+
+        let mut frames = format!("Initial state:\n{warehouse}");
+        while !warehouse.is_done() {
+            let direction = warehouse.directions[warehouse.moves];
+            warehouse.step();
+            frames += &format!("\nMove {direction:?}:\n{warehouse}");
+        }
+
+        assert_snapshot!("small_example_moves", frames);
     }
 
     #[test]
-    fn part_2() {
-        const EXPECTED: &str = r"
-####################
-##[].......[].[][]##
-##[]...........[].##
-##[]........[][][]##
-##[]......[]....[]##
-##..##......[]....##
-##..[]............##
-##..@......[].[][]##
-##......[][]..[]..##
-####################
-";
+    fn rewinding() {
+        let mut replay = Replay::with_interval(super::warehouse(Some(EXAMPLE), true), 16);
+        assert_eq!(replay.seek(10_000), 700);
+        let last = replay.render();
 
-        let super::Input { map, directions } = super::parse_input(Some(EXAMPLE));
+        assert_eq!(replay.seek(137), 137);
+        let after_137 = replay.render();
 
-        let mut map = map.to_wide_map();
+        let mut fresh = super::warehouse(Some(EXAMPLE), true);
+        for _ in 0..137 {
+            fresh.step();
+        }
+        assert_eq!(after_137, fresh.to_string());
 
-        super::move_robot(&mut map, &directions);
+        assert_eq!(replay.seek(700), 700);
+        assert_eq!(replay.render(), last);
+    }
 
-        assert_eq!(
-            map.to_string(),
-            EXPECTED.trim(),
-            "invalid map output\nours:\t\treference:\n{}",
-            std::iter::zip(map.to_string().lines(), EXPECTED.trim().lines())
-                .map(|(before, after)| format!("{}\t{}", before, after))
-                .collect::<Vec<_>>()
-                .join("\n")
-        );
+    #[test]
+    fn part_2() {
+        let map = super::warehouse(Some(EXAMPLE), true).finish();
+
+        assert_snapshot!("wide_map_after_moves", map);
 
         assert_eq!(super::part_2(Some(EXAMPLE)), 9021);
     }
+
+    #[test]
+    fn windows_line_endings() {
+        use crate::parse::crlf;
+
+        assert_eq!(super::part_1(Some(&crlf(SMALL_EXAMPLE))), 2028);
+        assert_eq!(super::part_1(Some(&crlf(EXAMPLE))), 10092);
+        assert_eq!(super::part_2(Some(&crlf(EXAMPLE))), 9021);
+    }
 }
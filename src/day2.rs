@@ -1,26 +1,50 @@
 use std::fmt::Debug;
 
-type Output = isize;
+use crate::{
+    answer::Answer,
+    inspect::Stats,
+    parse::{normalize, ParseError, ParseErrorKind, Span},
+};
 
-fn parse_input(input: Option<&str>) -> Vec<Vec<Output>> {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day2.input")
-    };
+type Output = isize;
 
-    input
+pub(crate) fn parse(input: &str) -> Result<Vec<Vec<Output>>, ParseError> {
+    Span::new(input)
         .trim()
         .lines()
         .map(|line| {
-            line.trim()
+            let report: Vec<_> = line
                 .split_whitespace()
-                .map(|el| el.parse().expect("Failed to parse number"))
-                .collect()
+                .map(|level| level.integer())
+                .collect::<Result<_, _>>()?;
+
+            if report.is_empty() {
+                Err(line.error(ParseErrorKind::Missing("levels".to_string())))
+            } else {
+                Ok(report)
+            }
         })
         .collect()
 }
 
+fn parse_input(input: Option<&str>) -> Vec<Vec<Output>> {
+    let input = input.unwrap_or(include_str!("./day2.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let reports = parse(&normalize(input))?;
+    Ok(Stats::new()
+        .count("reports", reports.len())
+        .histogram("levels per report", reports.iter().map(Vec::len)))
+}
+
 #[derive(Debug)]
 enum UnsafeReason {
     NonMonotonic(usize),
@@ -28,18 +52,21 @@ enum UnsafeReason {
 }
 
 fn is_report_safe<T: AsRef<[isize]> + Sized>(report: T) -> Option<UnsafeReason> {
-    let mut diffs = std::iter::zip(report.as_ref().iter(), (&report.as_ref()[1..]).iter())
-        .map(|(a, b)| b - a)
+    let mut diffs = report
+        .as_ref()
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
         .peekable();
 
-    let expected_sign = diffs.peek().expect("Got report with 0 levels").signum();
+    // reports with a single level have nothing that could be unsafe
+    let expected_sign = diffs.peek()?.signum();
 
     diffs.enumerate().find_map(|(idx, x)| {
         if x.signum() != expected_sign {
             Some(UnsafeReason::NonMonotonic(idx))
         } else {
             let x = x.abs();
-            if x >= 1 && x <= 3 {
+            if (1..=3).contains(&x) {
                 None
             } else {
                 Some(UnsafeReason::OutOfRangeJump(idx))
@@ -48,20 +75,21 @@ fn is_report_safe<T: AsRef<[isize]> + Sized>(report: T) -> Option<UnsafeReason>
     })
 }
 
-pub fn part_1(input: Option<&str>) -> usize {
+pub fn part_1(input: Option<&str>) -> Answer {
     parse_input(input)
         .into_iter()
         .filter(|report| is_report_safe(report).is_none())
         .count()
+        .into()
 }
 
-fn is_report_safe_without_single_element(report: &Vec<Output>, idx: usize) -> bool {
-    let mut report = report.clone();
+fn is_report_safe_without_single_element(report: &[Output], idx: usize) -> bool {
+    let mut report = report.to_vec();
     report.remove(idx);
     is_report_safe(report).is_none()
 }
 
-pub fn part_2(input: Option<&str>) -> usize {
+pub fn part_2(input: Option<&str>) -> Answer {
     parse_input(input)
         .into_iter()
         .filter(|report| match is_report_safe(report) {
@@ -77,6 +105,7 @@ pub fn part_2(input: Option<&str>) -> usize {
             None => true,
         })
         .count()
+        .into()
 }
 
 #[cfg(test)]
@@ -99,4 +128,20 @@ mod test {
     fn part_2() {
         assert_eq!(super::part_2(Some(EXAMPLE)), 4);
     }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example)), 2);
+        assert_eq!(super::part_2(Some(&example)), 4);
+    }
+
+    #[test]
+    fn inspecting() {
+        let stats = super::inspect(EXAMPLE).unwrap();
+        assert_eq!(
+            stats.to_string(),
+            "reports: 6\nlevels per report:\n  5  6\n"
+        );
+    }
 }
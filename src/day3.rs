@@ -1,42 +1,61 @@
 use regex::Regex;
 
-pub fn part_1(input: Option<&str>) -> usize {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day3.input")
-    };
+use crate::{
+    answer::Answer,
+    inspect::Stats,
+    parse::{normalize, ParseError},
+};
 
-    let re = Regex::new(r"mul\((?P<a>\d{1,3}),(?P<b>\d{1,3})\)").expect("invalid regex");
+const MUL: &str = r"mul\((?P<a>\d{1,3}),(?P<b>\d{1,3})\)";
 
-    re.captures_iter(input)
+/// Any text is a valid (if corrupted) memory dump.
+pub fn validate(_input: &str) -> Result<(), ParseError> {
+    Ok(())
+}
+
+/// Statistics about the shape of `input`, the instructions among the corrupted memory.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let input = normalize(input);
+    let re = Regex::new(&format!(r"{MUL}|do\(\)|don't\(\)")).expect("invalid regex");
+    let instructions = re.captures_iter(&input).map(|cap| match &cap[0] {
+        "do()" | "don't()" => cap[0].to_string(),
+        _ => "mul(a,b)".to_string(),
+    });
+
+    Ok(Stats::new()
+        .count("length", input.chars().count())
+        .histogram("instructions", instructions))
+}
+
+pub fn part_1(input: Option<&str>) -> Answer {
+    let input = normalize(input.unwrap_or(include_str!("./day3.input")));
+
+    let re = Regex::new(MUL).expect("invalid regex");
+
+    re.captures_iter(&input)
         .map(|cap| {
             let a = cap["a"].parse::<usize>().expect("invalid number");
             let b = cap["b"].parse::<usize>().expect("invalid number");
             a * b
         })
-        .sum()
+        .sum::<usize>()
+        .into()
 }
 
-pub fn part_2(input: Option<&str>) -> usize {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day3.input")
-    };
+pub fn part_2(input: Option<&str>) -> Answer {
+    let input = normalize(input.unwrap_or(include_str!("./day3.input")));
 
-    let re = Regex::new(r"mul\((?P<a>\d{1,3}),(?P<b>\d{1,3})\)|do\(\)|don't\(\)")
-        .expect("invalid regex");
+    let re = Regex::new(&format!(r"{MUL}|do\(\)|don't\(\)")).expect("invalid regex");
 
     let mut are_instructions_enabled = true;
-    re.captures_iter(input)
+    re.captures_iter(&input)
         .map(|cap| {
             if &cap[0] == "do()" {
                 are_instructions_enabled = true;
-                return 0;
+                0
             } else if &cap[0] == "don't()" {
                 are_instructions_enabled = false;
-                return 0;
+                0
             } else if are_instructions_enabled {
                 let a = cap["a"].parse::<usize>().expect("invalid number");
                 let b = cap["b"].parse::<usize>().expect("invalid number");
@@ -45,7 +64,8 @@ pub fn part_2(input: Option<&str>) -> usize {
                 0
             }
         })
-        .sum()
+        .sum::<usize>()
+        .into()
 }
 
 #[cfg(test)]
@@ -69,4 +89,16 @@ mod test {
             48
         );
     }
+
+    #[test]
+    fn windows_line_endings() {
+        let corrupted = crate::parse::crlf(
+            "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]\nthen(mul(11,8)mul(8,5))\n",
+        );
+        assert_eq!(super::part_1(Some(&corrupted)), 161);
+        let conditional = crate::parse::crlf(
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+\nmul(32,64](mul(11,8)undo()?mul(8,5))\n",
+        );
+        assert_eq!(super::part_2(Some(&conditional)), 48);
+    }
 }
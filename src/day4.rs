@@ -1,4 +1,10 @@
-fn count_xmas_at_pos(lines: &Vec<Vec<char>>, (pos_x, pos_y): (usize, usize)) -> usize {
+use crate::{
+    answer::Answer,
+    inspect::Stats,
+    parse::{normalize, ParseError, Span},
+};
+
+fn count_xmas_at_pos(lines: &[Vec<char>], (pos_x, pos_y): (usize, usize)) -> usize {
     const DIRECTIONS: &[[(isize, isize); 4]] = &[
         [(0, 0), (0, 1), (0, 2), (0, 3)],
         [(0, 0), (1, 0), (2, 0), (3, 0)],
@@ -29,7 +35,7 @@ fn count_xmas_at_pos(lines: &Vec<Vec<char>>, (pos_x, pos_y): (usize, usize)) ->
     count
 }
 
-fn check_for_x_mas_in_pos(lines: &Vec<Vec<char>>, (pos_x, pos_y): (usize, usize)) -> bool {
+fn check_for_x_mas_in_pos(lines: &[Vec<char>], (pos_x, pos_y): (usize, usize)) -> bool {
     const DIAGONALS: &[[(usize, usize); 3]] = &[[(0, 0), (1, 1), (2, 2)], [(2, 0), (1, 1), (0, 2)]];
 
     DIAGONALS.iter().all(|diag| {
@@ -41,21 +47,27 @@ fn check_for_x_mas_in_pos(lines: &Vec<Vec<char>>, (pos_x, pos_y): (usize, usize)
     })
 }
 
+pub(crate) fn parse(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    Span::new(input).char_grid()
+}
+
 fn parse_input(input: Option<&str>) -> Vec<Vec<char>> {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day4.input")
-    };
-
-    input
-        .trim()
-        .lines()
-        .map(|line| line.chars().collect())
-        .collect()
+    let input = input.unwrap_or(include_str!("./day4.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let grid = parse(&normalize(input))?;
+    Ok(Stats::new().grid(&grid))
 }
 
-pub fn part_1(input: Option<&str>) -> usize {
+pub fn part_1(input: Option<&str>) -> Answer {
     let lines = parse_input(input);
 
     let mut count = 0;
@@ -65,13 +77,13 @@ pub fn part_1(input: Option<&str>) -> usize {
         }
     }
 
-    count
+    count.into()
 }
 
-pub fn part_2(input: Option<&str>) -> usize {
+pub fn part_2(input: Option<&str>) -> Answer {
     let lines = parse_input(input);
 
-    let mut count = 0;
+    let mut count: usize = 0;
     for i in 0..lines.len() {
         for j in 0..lines[i].len() {
             if check_for_x_mas_in_pos(&lines, (i, j)) {
@@ -80,7 +92,7 @@ pub fn part_2(input: Option<&str>) -> usize {
         }
     }
 
-    count
+    count.into()
 }
 
 #[cfg(test)]
@@ -107,4 +119,11 @@ MXMXAXMASX
     fn part_2() {
         assert_eq!(super::part_2(Some(EXAMPLE)), 9);
     }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example)), 18);
+        assert_eq!(super::part_2(Some(&example)), 9);
+    }
 }
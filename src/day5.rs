@@ -1,56 +1,80 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{
+    answer::Answer,
+    inspect::Stats,
+    parse::{normalize, ParseError, Span},
+};
 
 type Num = u8;
 
-struct Output {
-    before_map: HashMap<Num, Vec<Num>>,
-    page_updates: Vec<Vec<Num>>,
+pub(crate) struct Output {
+    pub(crate) before_map: HashMap<Num, Vec<Num>>,
+    pub(crate) page_updates: Vec<Vec<Num>>,
 }
 
-fn parse_input(input: Option<&str>) -> Output {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day5.input")
-    }
-    .trim();
-
-    let (spec_str, update_str) = input
-        .split_once("\n\n")
-        .expect("Invalid input: couldn't split into sections");
-
-    let spec = spec_str.lines().map(|line| {
-        let (before, after) = line
-            .split_once("|")
-            .expect("Invalid input: couldn't split into before and after");
-        let before = before
-            .parse()
-            .expect("Invalid input: couldn't parse before");
-        let after = after.parse().expect("Invalid input: couldn't parse after");
-        (before, after)
-    });
-
-    let before_map = spec.fold(HashMap::new(), |mut before_map, (before, after)| {
-        before_map
-            .entry(before)
-            .or_insert_with(Vec::new)
-            .push(after);
-        before_map
-    });
-
-    let page_updates = update_str
+pub(crate) fn parse(input: &str) -> Result<Output, ParseError> {
+    let [spec, updates] = Span::new(input).sections_exact()?;
+
+    let spec = spec
         .lines()
         .map(|line| {
-            line.split(",")
-                .map(|num| num.parse().expect("Invalid input: couldn't parse number"))
-                .collect()
+            let (before, after) = line.split_once("|")?;
+            Ok((before.integer()?, after.integer()?))
         })
-        .collect();
+        .collect::<Result<Vec<(Num, Num)>, ParseError>>()?;
 
-    Output {
+    let before_map = spec
+        .into_iter()
+        .fold(HashMap::new(), |mut before_map, (before, after)| {
+            before_map
+                .entry(before)
+                .or_insert_with(Vec::new)
+                .push(after);
+            before_map
+        });
+
+    let page_updates = updates
+        .lines()
+        .map(|line| line.split(",").map(|num| num.integer()).collect())
+        .collect::<Result<_, _>>()?;
+
+    Ok(Output {
         before_map,
         page_updates,
-    }
+    })
+}
+
+fn parse_input(input: Option<&str>) -> Output {
+    let input = input.unwrap_or(include_str!("./day5.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let Output {
+        before_map,
+        page_updates,
+    } = parse(&normalize(input))?;
+
+    let pages: HashSet<_> = before_map
+        .iter()
+        .flat_map(|(before, afters)| afters.iter().chain([before]))
+        .collect();
+
+    Ok(Stats::new()
+        .count("pages in rules", pages.len())
+        .count("rules", before_map.values().map(Vec::len).sum())
+        .count("updates", page_updates.len())
+        .histogram("pages per update", page_updates.iter().map(Vec::len)))
 }
 
 fn get_page_updates_cmp(
@@ -77,7 +101,7 @@ fn get_page_updates_cmp(
     }
 }
 
-pub fn part_1(input: Option<&str>) -> u16 {
+pub fn part_1(input: Option<&str>) -> Answer {
     let Output {
         before_map,
         page_updates,
@@ -90,10 +114,11 @@ pub fn part_1(input: Option<&str>) -> u16 {
         .filter(|page_update| page_update.is_sorted_by(|a, b| page_updates_cmp(a, b).is_ge()))
         .map(|page_update| page_update[page_update.len() / 2])
         .map(u16::from)
-        .sum()
+        .sum::<u16>()
+        .into()
 }
 
-pub fn part_2(input: Option<&str>) -> u16 {
+pub fn part_2(input: Option<&str>) -> Answer {
     let Output {
         before_map,
         page_updates,
@@ -109,7 +134,8 @@ pub fn part_2(input: Option<&str>) -> u16 {
             page_update[page_update.len() / 2]
         })
         .map(u16::from)
-        .sum()
+        .sum::<u16>()
+        .into()
 }
 
 #[cfg(test)]
@@ -154,4 +180,11 @@ mod test {
     fn part_2() {
         assert_eq!(super::part_2(Some(EXAMPLE)), 123);
     }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example)), 143);
+        assert_eq!(super::part_2(Some(&example)), 123);
+    }
 }
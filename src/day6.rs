@@ -1,7 +1,17 @@
-use std::collections::HashSet;
+use std::fmt::{self, Display};
+
+use crate::{
+    answer::Answer,
+    bitgrid::BitGrid,
+    cancel,
+    inspect::Stats,
+    parse::{normalize, ParseError, ParseErrorKind, Span},
+    progress,
+    simulation::Simulation,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
+pub enum Direction {
     Up,
     Down,
     Left,
@@ -23,20 +33,46 @@ type PosComp = isize;
 type Pos = (PosComp, PosComp);
 type GuardState = (Pos, Direction);
 
+/// The states the guard has been in, a grid of positions per direction.
+struct Visited([BitGrid; 4]);
+
+impl Visited {
+    fn new(width: PosComp, height: PosComp) -> Self {
+        Self([(); 4].map(|_| BitGrid::new(width as usize, height as usize)))
+    }
+
+    /// Records a state of a guard inside the map, returning whether it's a new one.
+    fn insert(&mut self, ((x, y), direction): GuardState) -> bool {
+        self.0[direction as usize].insert(x as usize, y as usize)
+    }
+
+    fn clear(&mut self) {
+        self.0.iter_mut().for_each(BitGrid::clear);
+    }
+
+    fn positions(&self) -> BitGrid {
+        let [up, down, left, right] = &self.0;
+        &(&(up | down) | left) | right
+    }
+}
+
+/// The lab with the guard walking through it, one step or turn at a time.
 #[derive(Clone)]
-struct State {
-    obstacles: HashSet<Pos>,
-    guard: GuardState,
-    width: PosComp,
-    height: PosComp,
+pub struct State {
+    pub(crate) obstacles: BitGrid,
+    pub(crate) guard: GuardState,
+    pub(crate) width: PosComp,
+    pub(crate) height: PosComp,
 }
 
-impl State {
-    fn take_step(&mut self) {
+impl Simulation for State {
+    type Snapshot = GuardState;
+
+    fn step(&mut self) {
         let step = self.guard.1.get_step();
         let new_guard_pos = (self.guard.0 .0 + step.0, self.guard.0 .1 + step.1);
 
-        if !self.obstacles.contains(&new_guard_pos) {
+        if !self.is_obstacle(new_guard_pos) {
             self.guard.0 = new_guard_pos;
         } else {
             self.guard.1 = match self.guard.1 {
@@ -48,6 +84,26 @@ impl State {
         }
     }
 
+    /// Done once the guard left, which it never does when it's stuck in a loop.
+    fn is_done(&self) -> bool {
+        !self.is_guard_inside()
+    }
+
+    fn snapshot(&self) -> GuardState {
+        self.guard
+    }
+
+    fn restore(&mut self, guard: &GuardState) {
+        self.guard = *guard;
+    }
+}
+
+impl State {
+    fn is_obstacle(&self, (x, y): Pos) -> bool {
+        // negative positions wrap around to huge ones, which are outside of the grid as well
+        self.obstacles.contains(x as usize, y as usize)
+    }
+
     fn is_guard_inside(&self) -> bool {
         self.guard.0 .0 >= 0
             && self.guard.0 .0 < self.width
@@ -56,99 +112,152 @@ impl State {
     }
 }
 
-fn parse_input(input: Option<&str>) -> State {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day6.input")
-    }
-    .trim();
-
-    let obstacles = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars().enumerate().filter_map(move |(x, c)| {
-                if c == '#' {
-                    Some((x as PosComp, y as PosComp))
+impl Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let c = if self.guard.0 == (x, y) {
+                    match self.guard.1 {
+                        Direction::Up => '^',
+                        Direction::Down => 'v',
+                        Direction::Left => '<',
+                        Direction::Right => '>',
+                    }
+                } else if self.is_obstacle((x, y)) {
+                    '#'
                 } else {
-                    None
-                }
-            })
-        })
-        .collect();
-
-    let guard = input
-        .lines()
-        .enumerate()
-        .find_map(|(y, line)| {
-            line.chars().enumerate().find_map(|(x, c)| match c {
-                '^' => Some(((x as PosComp, y as PosComp), Direction::Up)),
-                'v' => Some(((x as PosComp, y as PosComp), Direction::Down)),
-                '<' => Some(((x as PosComp, y as PosComp), Direction::Left)),
-                '>' => Some(((x as PosComp, y as PosComp), Direction::Right)),
-                _ => None,
-            })
+                    '.'
+                };
+
+                write!(f, "{c}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn parse(input: &str) -> Result<State, ParseError> {
+    let span = Span::new(input);
+    let grid = span.char_grid()?;
+
+    let cells = grid.iter().enumerate().flat_map(|(y, row)| {
+        row.iter()
+            .enumerate()
+            .map(move |(x, &c)| ((x as PosComp, y as PosComp), c))
+    });
+
+    let width = grid[0].len() as PosComp;
+    let height = grid.len() as PosComp;
+
+    let mut obstacles = BitGrid::new(width as usize, height as usize);
+    for ((x, y), _) in cells.clone().filter(|&(_, c)| c == '#') {
+        obstacles.insert(x as usize, y as usize);
+    }
+
+    let guard = cells
+        .filter_map(|(pos, c)| match c {
+            '^' => Some((pos, Direction::Up)),
+            'v' => Some((pos, Direction::Down)),
+            '<' => Some((pos, Direction::Left)),
+            '>' => Some((pos, Direction::Right)),
+            _ => None,
         })
-        .expect("No guard found");
+        .next()
+        .ok_or_else(|| span.error(ParseErrorKind::Missing("guard".to_string())))?;
 
-    State {
+    Ok(State {
         obstacles,
         guard,
-        width: input.lines().next().unwrap().len() as PosComp,
-        height: input.lines().count() as PosComp,
-    }
+        width,
+        height,
+    })
+}
+
+fn parse_input(input: Option<&str>) -> State {
+    let input = input.unwrap_or(include_str!("./day6.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
 }
 
-pub fn part_1(input: Option<&str>) -> usize {
+/// The lab before the guard moves.
+pub fn patrol(input: Option<&str>) -> State {
+    parse_input(input)
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let input = normalize(input);
+    let lab = parse(&input)?;
+
+    Ok(Stats::new()
+        .grid(&Span::new(&input).char_grid()?)
+        .count("obstacles", lab.obstacles.count_ones()))
+}
+
+pub fn part_1(input: Option<&str>) -> Answer {
     let mut state = parse_input(input);
 
-    let mut visited = HashSet::new();
-    while state.is_guard_inside() {
-        visited.insert(state.guard);
-        state.take_step();
+    // the guard might be stuck in a loop without any extra obstacle
+    let mut visited = Visited::new(state.width, state.height);
+    while !state.is_done() && visited.insert(state.guard) {
+        cancel::check();
+        state.step();
     }
 
-    visited
-        .iter()
-        .map(|guard| guard.0)
-        .collect::<HashSet<_>>()
-        .len()
+    visited.positions().count_ones().into()
 }
 
-pub fn part_2(input: Option<&str>) -> usize {
+pub fn part_2(input: Option<&str>) -> Answer {
     // extremely inefficient, but it works ¯\_(ツ)_/¯
-    let state = parse_input(input);
+    let mut state = parse_input(input);
+    let start = state.guard;
 
     let positions = (0..state.width).flat_map(move |x| (0..state.height).map(move |y| (x, y)));
 
-    let mut count = 0;
+    let mut count: usize = 0;
+    let mut visited = Visited::new(state.width, state.height);
 
+    progress::start("placing obstructions", (state.width * state.height) as u64);
     for pos in positions {
-        let mut state = state.clone();
+        cancel::check();
+        progress::advance();
 
-        if state.obstacles.contains(&pos) || state.guard.0 == pos {
+        if state.is_obstacle(pos) || start.0 == pos {
             continue;
         }
 
-        state.obstacles.insert(pos);
+        state.guard = start;
+        state.obstacles.insert(pos.0 as usize, pos.1 as usize);
+        visited.clear();
 
-        let mut visited = HashSet::new();
-        while state.is_guard_inside() && !visited.contains(&state.guard) {
-            visited.insert(state.guard);
-            state.take_step();
+        while !state.is_done() && visited.insert(state.guard) {
+            state.step();
         }
 
         if state.is_guard_inside() {
             count += 1;
         }
+
+        state.obstacles.remove(pos.0 as usize, pos.1 as usize);
     }
 
-    count
+    count.into()
 }
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashSet;
+
+    use super::parse_input;
+    use crate::{simulation::Simulation, snapshot::assert_snapshot};
+
     static EXAMPLE: &str = r"
 ....#.....
 .........#
@@ -171,4 +280,44 @@ mod test {
     fn part_2() {
         assert_eq!(super::part_2(Some(EXAMPLE)), 6);
     }
+
+    #[test]
+    fn guard_path() {
+        let mut state = parse_input(Some(EXAMPLE));
+        let initial_map = state.to_string();
+
+        let mut visited = HashSet::new();
+        while !state.is_done() {
+            visited.insert(state.guard.0);
+            state.step();
+        }
+
+        let path = initial_map
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        if visited.contains(&(x as isize, y as isize)) {
+                            'X'
+                        } else {
+                            c
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_snapshot!("initial_map", initial_map);
+        assert_snapshot!("guard_path", path);
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example)), 41);
+        assert_eq!(super::part_2(Some(&example)), 6);
+    }
 }
@@ -1,9 +1,16 @@
+use crate::{
+    answer::Answer,
+    cancel,
+    inspect::Stats,
+    parse::{normalize, ParseError, ParseErrorKind, Span},
+};
+
 type Num = usize;
 
 #[derive(Debug, Clone)]
-struct Equation {
-    result: Num,
-    operands: Vec<Num>,
+pub(crate) struct Equation {
+    pub(crate) result: Num,
+    pub(crate) operands: Vec<Num>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -28,7 +35,7 @@ fn permutations<T: Clone>(source: &[T], n: u32) -> impl Iterator<Item = Vec<T>>
     (0..amount.pow(n)).map(move |i| {
         (0..n)
             .map(|j| {
-                let shift = amount.pow(j as u32);
+                let shift = amount.pow(j);
                 source[(i / shift) % amount].clone()
             })
             .collect()
@@ -41,6 +48,7 @@ impl Equation {
         let mut operator_combinations = permutations(operators, operands.len() as u32);
 
         operator_combinations.any(|operators| {
+            cancel::check();
             self.result
                 == std::iter::zip(operands, operators)
                     .fold(self.operands[0], |acc, (operand, op)| {
@@ -50,53 +58,64 @@ impl Equation {
     }
 }
 
-fn parse_input(input: Option<&str>) -> Vec<Equation> {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day7.input")
-    }
-    .trim();
-
-    input
+pub(crate) fn parse(input: &str) -> Result<Vec<Equation>, ParseError> {
+    Span::new(input)
+        .trim()
         .lines()
         .map(|line| {
-            let (result_str, operands_str) = line
-                .split_once(": ")
-                .expect("Invalid input: couldn't split into result and operands");
-            let result = result_str
-                .parse()
-                .expect("Invalid input: couldn't parse result");
-
-            let operands = operands_str
+            let (result, operands_span) = line.split_once(": ")?;
+            let operands: Vec<_> = operands_span
                 .split_whitespace()
-                .map(|operand_str| {
-                    operand_str
-                        .parse()
-                        .expect("Invalid input: couldn't parse operand")
-                })
-                .collect();
-
-            Equation { result, operands }
+                .map(|operand| operand.integer())
+                .collect::<Result<_, _>>()?;
+
+            if operands.is_empty() {
+                return Err(operands_span.error(ParseErrorKind::Missing("operands".to_string())));
+            }
+
+            Ok(Equation {
+                result: result.integer()?,
+                operands,
+            })
         })
         .collect()
 }
 
-fn sum_solvable_results(input: Option<&str>, operators: &[Operator]) -> Num {
+fn parse_input(input: Option<&str>) -> Vec<Equation> {
+    let input = input.unwrap_or(include_str!("./day7.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let equations = parse(&normalize(input))?;
+    Ok(Stats::new().count("equations", equations.len()).histogram(
+        "operands per equation",
+        equations.iter().map(|equation| equation.operands.len()),
+    ))
+}
+
+fn sum_solvable_results(input: Option<&str>, operators: &[Operator]) -> Answer {
     let equations = parse_input(input);
 
     equations
         .iter()
         .filter(|eq| eq.is_solvable(operators))
         .map(|eq| eq.result)
-        .sum()
+        .sum::<Num>()
+        .into()
 }
 
-pub fn part_1(input: Option<&str>) -> Num {
+pub fn part_1(input: Option<&str>) -> Answer {
     sum_solvable_results(input, &[Operator::Add, Operator::Multiply])
 }
 
-pub fn part_2(input: Option<&str>) -> Num {
+pub fn part_2(input: Option<&str>) -> Answer {
     sum_solvable_results(
         input,
         &[Operator::Add, Operator::Multiply, Operator::Concatenation],
@@ -164,4 +183,11 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example)), 3749);
+        assert_eq!(super::part_2(Some(&example)), 11387);
+    }
 }
@@ -1,12 +1,16 @@
 use itertools::{iproduct, Itertools};
-use std::{
-    collections::HashMap,
-    ops::{Div, Sub},
+use std::{collections::HashMap, ops::Sub};
+
+use crate::{
+    answer::Answer,
+    inspect::Stats,
+    math,
+    parse::{normalize, ParseError, Span},
 };
 
 type PosNum = isize;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos(PosNum, PosNum);
+pub(crate) struct Pos(pub(crate) PosNum, pub(crate) PosNum);
 
 impl Sub for &Pos {
     type Output = Pos;
@@ -15,47 +19,39 @@ impl Sub for &Pos {
     }
 }
 
-impl Div for Pos {
-    type Output = Option<f32>;
-    fn div(self, rhs: Self) -> Self::Output {
-        if rhs.0 == 0 || rhs.1 == 0 {
-            None
-        } else {
-            let div_x = self.0 as f32 / rhs.0 as f32;
-            let div_y = self.1 as f32 / rhs.1 as f32;
-
-            if div_x == div_y {
-                Some(div_x)
+impl Pos {
+    /// Whether `self` is `other` times some integer.
+    fn is_multiple_of(self, other: Pos) -> bool {
+        let [x, y, other_x, other_y] = [self.0, self.1, other.0, other.1].map(|n| n as i128);
+
+        // parallel, and a whole number of steps apart along the axis `other` moves on
+        math::det(x, other_x, y, other_y) == Some(0)
+            && if other_x != 0 {
+                math::exact_div(x, other_x).is_some()
             } else {
-                None
+                math::exact_div(y, other_y).is_some()
             }
-        }
     }
 }
 
 #[derive(Debug)]
-struct Map {
-    width: usize,
-    height: usize,
-    antennas: HashMap<char, Vec<Pos>>,
+pub(crate) struct Map {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) antennas: HashMap<char, Vec<Pos>>,
 }
 
-fn parse_input(input: Option<&str>) -> Map {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day8.input")
-    }
-    .trim();
+pub(crate) fn parse(input: &str) -> Result<Map, ParseError> {
+    let grid = Span::new(input).char_grid()?;
 
-    let antennas = input
-        .lines()
+    let antennas = grid
+        .iter()
         .enumerate()
         .flat_map(|(y, line)| {
-            line.chars()
+            line.iter()
                 .enumerate()
                 .filter(|(_, ch)| ch.is_alphanumeric())
-                .map(move |(x, c)| (c, Pos(x as PosNum, y as PosNum)))
+                .map(move |(x, &c)| (c, Pos(x as PosNum, y as PosNum)))
         })
         .fold(
             HashMap::new(),
@@ -65,18 +61,43 @@ fn parse_input(input: Option<&str>) -> Map {
             },
         );
 
-    Map {
+    Ok(Map {
         antennas,
-        width: input.lines().next().unwrap().len(),
-        height: input.lines().count(),
-    }
+        width: grid[0].len(),
+        height: grid.len(),
+    })
+}
+
+fn parse_input(input: Option<&str>) -> Map {
+    let input = input.unwrap_or(include_str!("./day8.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let input = normalize(input);
+    let map = parse(&input)?;
+
+    let antennas = map
+        .antennas
+        .iter()
+        .flat_map(|(&frequency, positions)| positions.iter().map(move |_| frequency));
+    Ok(Stats::new()
+        .grid(&Span::new(&input).char_grid()?)
+        .count("frequencies", map.antennas.len())
+        .histogram("antennas per frequency", antennas))
 }
 
 fn get_all_positions(width: usize, height: usize) -> impl Iterator<Item = Pos> {
     iproduct!(0..width, 0..height).map(|(x, y)| Pos(x as PosNum, y as PosNum))
 }
 
-pub fn part_1(input: Option<&str>) -> usize {
+pub fn part_1(input: Option<&str>) -> Answer {
     let map = parse_input(input);
 
     get_all_positions(map.width, map.height)
@@ -89,26 +110,23 @@ pub fn part_1(input: Option<&str>) -> usize {
             })
         })
         .count()
+        .into()
 }
 
-pub fn part_2(input: Option<&str>) -> usize {
+pub fn part_2(input: Option<&str>) -> Answer {
     let map = parse_input(input);
-    println!("{:?}", map);
 
     get_all_positions(map.width, map.height)
         .filter(|pos| {
             map.antennas.values().any(|antenna_positions| {
-                antenna_positions.iter().tuple_combinations().any(|(a, b)| {
-                    ((pos - a) / (a - b))
-                        .map(|x| x.fract() == 0.0)
-                        .unwrap_or(false)
-                        || ((pos - b) / (b - a))
-                            .map(|x| x.fract() == 0.0)
-                            .unwrap_or(false)
-                })
+                antenna_positions
+                    .iter()
+                    .tuple_combinations()
+                    .any(|(a, b)| (pos - a).is_multiple_of(a - b))
             })
         })
         .count()
+        .into()
 }
 
 #[cfg(test)]
@@ -137,4 +155,11 @@ mod test {
     fn part_2() {
         assert_eq!(super::part_2(Some(EXAMPLE)), 34);
     }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example)), 14);
+        assert_eq!(super::part_2(Some(&example)), 34);
+    }
 }
@@ -1,48 +1,79 @@
 use itertools::Itertools;
 
+use crate::{
+    answer::Answer,
+    cancel,
+    inspect::Stats,
+    parse::{normalize, ParseError, Span},
+    progress,
+};
+
 #[derive(Debug)]
-struct File {
-    id: usize,
-    size: u8,
+pub(crate) struct File {
+    pub(crate) id: usize,
+    pub(crate) size: u8,
 }
 
 #[derive(Debug)]
-enum DiskEntry {
+pub(crate) enum DiskEntry {
     File(File),
     FreeSpace(u8),
 }
 
-type DiskMap = Vec<DiskEntry>;
+pub(crate) type DiskMap = Vec<DiskEntry>;
 
-fn parse_input(input: Option<&str>) -> DiskMap {
-    let input = if let Some(input) = input {
-        input
-    } else {
-        include_str!("./day9.input")
-    }
-    .trim();
+pub(crate) fn parse(input: &str) -> Result<DiskMap, ParseError> {
+    let sizes = Span::new(input).non_empty()?.digits()?;
 
-    let mut file_count = 0;
-    input
-        .chars()
+    Ok(sizes
+        .into_iter()
         .enumerate()
-        .map(|(i, c)| {
-            let size = c.to_digit(10).expect("Invalid input: couldn't parse size") as u8;
+        .map(|(i, size)| {
             if i % 2 == 0 {
-                let id = file_count;
-                file_count += 1;
-                DiskEntry::File(File { id, size })
+                DiskEntry::File(File { id: i / 2, size })
             } else {
                 DiskEntry::FreeSpace(size)
             }
         })
-        .collect()
+        .collect())
+}
+
+fn parse_input(input: Option<&str>) -> DiskMap {
+    let input = input.unwrap_or(include_str!("./day9.input"));
+    parse(&normalize(input)).unwrap_or_else(|err| panic!("Invalid input: {err}"))
+}
+
+/// Checks whether `input` is a valid input for this day.
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse(&normalize(input)).map(|_| ())
+}
+
+/// Statistics about the shape of `input`.
+pub fn inspect(input: &str) -> Result<Stats, ParseError> {
+    let disk = parse(&normalize(input))?;
+
+    let (mut files, mut file_blocks, mut free_blocks) = (0, 0, 0);
+    for entry in &disk {
+        match entry {
+            DiskEntry::File(file) => {
+                files += 1;
+                file_blocks += usize::from(file.size);
+            }
+            DiskEntry::FreeSpace(size) => free_blocks += usize::from(*size),
+        }
+    }
+
+    Ok(Stats::new()
+        .count("disk length", file_blocks + free_blocks)
+        .count("files", files)
+        .count("file blocks", file_blocks)
+        .count("free blocks", free_blocks))
 }
 
 type NumericalRepr = Vec<Option<usize>>;
 
-fn get_numerical_representation(disk: &Vec<DiskEntry>) -> NumericalRepr {
-    disk.into_iter()
+fn get_numerical_representation(disk: &[DiskEntry]) -> NumericalRepr {
+    disk.iter()
         .flat_map(|entry| match entry {
             DiskEntry::File(File { id, size }) => {
                 std::iter::repeat_n(Some(*id), usize::from(*size))
@@ -59,14 +90,14 @@ fn numerical_representation_to_string(disk: &NumericalRepr) -> String {
         .join("")
 }
 
-pub fn part_1(input: Option<&str>) -> usize {
+pub fn part_1(input: Option<&str>) -> Answer {
     let map = parse_input(input);
 
     let mut disk = get_numerical_representation(&map);
 
     let mut compacted_disk = vec![];
 
-    while disk.len() > 0 {
+    while !disk.is_empty() {
         let el = disk.remove(0);
         if let Some(el) = el {
             compacted_disk.push(el);
@@ -74,9 +105,10 @@ pub fn part_1(input: Option<&str>) -> usize {
             while disk.last() == Some(&None) {
                 disk.pop();
             }
-            compacted_disk.push(disk.pop().expect("Invalid input: couldn't pop").expect(
-                "Unreachable: element is None even though we removed all trailing 'None's just now",
-            ));
+            // the free space might have been at the very end, with nothing left to move into it
+            if let Some(el) = disk.pop().flatten() {
+                compacted_disk.push(el);
+            }
         }
     }
 
@@ -84,10 +116,11 @@ pub fn part_1(input: Option<&str>) -> usize {
         .iter()
         .enumerate()
         .map(|(i, el)| el * i)
-        .sum()
+        .sum::<usize>()
+        .into()
 }
 
-pub fn part_2(input: Option<&str>) -> usize {
+pub fn part_2(input: Option<&str>) -> Answer {
     let mut disk = parse_input(input);
 
     let &max_id = disk
@@ -99,7 +132,11 @@ pub fn part_2(input: Option<&str>) -> usize {
         })
         .expect("Invalid input: no files found");
 
+    progress::start("moving files", max_id as u64 + 1);
     for id in (0..=max_id).rev() {
+        cancel::check();
+        progress::advance();
+
         let (file_idx, file_size) = disk
             .iter()
             .enumerate()
@@ -142,7 +179,8 @@ pub fn part_2(input: Option<&str>) -> usize {
         .iter()
         .enumerate()
         .map(|(i, el)| el.map(|el| el * i).unwrap_or(0))
-        .sum()
+        .sum::<usize>()
+        .into()
 }
 
 #[cfg(test)]
@@ -160,4 +198,20 @@ mod test {
     fn part_2() {
         assert_eq!(super::part_2(Some(EXAMPLE)), 2858);
     }
+
+    #[test]
+    fn windows_line_endings() {
+        let example = crate::parse::crlf(EXAMPLE);
+        assert_eq!(super::part_1(Some(&example)), 1928);
+        assert_eq!(super::part_2(Some(&example)), 2858);
+    }
+
+    #[test]
+    fn inspecting() {
+        let stats = super::inspect(EXAMPLE).unwrap();
+        assert_eq!(
+            stats.to_string(),
+            "disk length: 42\nfiles: 10\nfile blocks: 28\nfree blocks: 14\n"
+        );
+    }
 }
@@ -0,0 +1,612 @@
+//! Registry of every solved day, so that front ends (the CLI, the HTTP service) can list the days
+//! and run their parts without matching on day numbers themselves.
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+#[cfg(feature = "image")]
+use crate::image::Image;
+use crate::{
+    answer::Answer,
+    cancel::{self, Failure},
+    inspect::Stats,
+    parse::ParseError,
+    progress::{self, Tracker},
+    simulation::{Player, Replay, Simulation},
+};
+
+#[derive(Debug, Serialize)]
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+    /// The bundled input, which the solutions use when they aren't given one.
+    #[serde(skip)]
+    pub input: &'static str,
+    #[serde(skip)]
+    pub validate: fn(&str) -> Result<(), ParseError>,
+    /// Statistics about the shape of an input, which has to be valid.
+    #[serde(skip)]
+    pub inspect: fn(&str) -> Result<Stats, ParseError>,
+    pub parts: [Part; 2],
+}
+
+#[derive(Debug, Serialize)]
+pub struct Part {
+    pub part: u8,
+    pub params: &'static [Param],
+    #[serde(skip)]
+    solve: fn(Option<&str>, &Params) -> Answer,
+    /// The simulation the part runs, for the parts which are one.
+    #[serde(skip)]
+    simulate: Option<Simulate>,
+    /// The picture of the part's grid, for the parts which have one.
+    #[cfg(feature = "image")]
+    #[serde(skip)]
+    draw: Option<Draw>,
+}
+
+/// Sets up the simulation of a part, see [Part::simulation].
+type Simulate = fn(Option<&str>, &Params) -> Box<dyn Player>;
+
+/// Draws the state of a part's grid, see [Part::image], or gives [None] if there's nothing to draw
+/// for the input.
+#[cfg(feature = "image")]
+type Draw = fn(Option<&str>, &Params) -> Option<Image>;
+
+/// The answer of a part and how long it took to compute.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub answer: Answer,
+    pub duration: Duration,
+}
+
+/// An integer parameter of a part, e.g. the size of the grid or the number of steps to simulate,
+/// which differs between the examples and the real puzzle.
+#[derive(Debug, Serialize)]
+pub struct Param {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default: i64,
+    pub min: i64,
+    pub max: i64,
+}
+
+/// The values of a part's parameters, checked against their declared ranges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params(HashMap<&'static str, i64>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    Unknown(String),
+    Invalid {
+        name: String,
+        value: String,
+    },
+    OutOfRange {
+        name: String,
+        value: i64,
+        min: i64,
+        max: i64,
+    },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(f, "unknown parameter {name:?}"),
+            Self::Invalid { name, value } => {
+                write!(f, "invalid value {value:?} for parameter {name:?}")
+            }
+            Self::OutOfRange {
+                name,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "value {value} for parameter {name:?} isn't between {min} and {max}"
+            ),
+        }
+    }
+}
+
+impl Error for ParamError {}
+
+impl Params {
+    /// # Panics
+    ///
+    /// If the part has no parameter called `name`.
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> T {
+        let value = *self
+            .0
+            .get(name)
+            .unwrap_or_else(|| panic!("unknown parameter {name:?}"));
+
+        T::try_from(value)
+            .unwrap_or_else(|_| panic!("parameter {name:?} was checked against its range"))
+    }
+
+    /// Every parameter's name and value, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, i64)> {
+        let mut values: Vec<_> = self.0.iter().map(|(&name, &value)| (name, value)).collect();
+        values.sort();
+        values.into_iter()
+    }
+}
+
+impl Part {
+    /// The parameters of this part with the given values instead of the defaults.
+    pub fn params<'a>(
+        &self,
+        values: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Params, ParamError> {
+        let mut params: HashMap<_, _> = self
+            .params
+            .iter()
+            .map(|param| (param.name, param.default))
+            .collect();
+
+        for (name, value) in values {
+            let param = self
+                .params
+                .iter()
+                .find(|param| param.name == name)
+                .ok_or_else(|| ParamError::Unknown(name.to_string()))?;
+
+            let value = value.parse().map_err(|_| ParamError::Invalid {
+                name: name.to_string(),
+                value: value.to_string(),
+            })?;
+
+            if !(param.min..=param.max).contains(&value) {
+                return Err(ParamError::OutOfRange {
+                    name: name.to_string(),
+                    value,
+                    min: param.min,
+                    max: param.max,
+                });
+            }
+
+            params.insert(param.name, value);
+        }
+
+        Ok(Params(params))
+    }
+
+    /// Solves this part for `input`, or the bundled puzzle input if there's none.
+    ///
+    /// # Panics
+    ///
+    /// If `input` isn't valid for this day, see [Day::validate].
+    pub fn solve(&self, input: Option<&str>, params: &Params) -> Answer {
+        (self.solve)(input, params)
+    }
+
+    /// The simulation of this part on `input`, or the bundled puzzle input if there's none, at its
+    /// first step. Only some parts are simulations.
+    ///
+    /// # Panics
+    ///
+    /// If `input` isn't valid for this day, see [Day::validate].
+    pub fn simulation(&self, input: Option<&str>, params: &Params) -> Option<Box<dyn Player>> {
+        self.simulate.map(|simulate| simulate(input, params))
+    }
+
+    /// The picture of this part's grid for `input`, or the bundled puzzle input if there's none.
+    /// Only some parts have one, and only for some inputs, e.g. day 14's robots don't draw a
+    /// picture in every room.
+    ///
+    /// # Panics
+    ///
+    /// If `input` isn't valid for this day, see [Day::validate].
+    #[cfg(feature = "image")]
+    pub fn image(&self, input: Option<&str>, params: &Params) -> Option<Image> {
+        self.draw.and_then(|draw| draw(input, params))
+    }
+
+    /// Solves this part on its own thread, stopping it once `timeout` has passed and recording
+    /// its progress into `tracker`. A panic, e.g. because of an invalid input, is reported as a
+    /// [Failure] too.
+    pub fn run(
+        &self,
+        input: Option<&str>,
+        params: &Params,
+        timeout: Option<Duration>,
+        tracker: Option<Arc<Tracker>>,
+    ) -> Result<Run, Failure> {
+        cancel::with_timeout(timeout, || {
+            let solve = || {
+                let start = Instant::now();
+                let answer = self.solve(input, params);
+
+                Run {
+                    answer,
+                    duration: start.elapsed(),
+                }
+            };
+
+            match tracker {
+                Some(tracker) => progress::track(tracker, solve),
+                None => solve(),
+            }
+        })
+    }
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Option<&Part> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+}
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+// unused if no day is enabled
+#[allow(dead_code)]
+const fn part(
+    part: u8,
+    params: &'static [Param],
+    solve: fn(Option<&str>, &Params) -> Answer,
+) -> Part {
+    Part {
+        part,
+        params,
+        solve,
+        simulate: None,
+        #[cfg(feature = "image")]
+        draw: None,
+    }
+}
+
+// unused if no simulated day is enabled
+#[allow(dead_code)]
+fn replay(simulation: impl Simulation + 'static) -> Box<dyn Player> {
+    Box::new(Replay::new(simulation))
+}
+
+impl Part {
+    // unused if no simulated day is enabled
+    #[allow(dead_code)]
+    const fn simulated(self, simulate: Simulate) -> Self {
+        Self {
+            simulate: Some(simulate),
+            ..self
+        }
+    }
+
+    // unused if no drawn day is enabled
+    #[cfg(feature = "image")]
+    #[allow(dead_code)]
+    const fn drawn(self, draw: Draw) -> Self {
+        Self {
+            draw: Some(draw),
+            ..self
+        }
+    }
+}
+
+/// `part` with the picture `draw` of its grid, see [Part::image], if the `image` feature is on.
+// unused if no drawn day is enabled
+#[allow(unused_macros)]
+macro_rules! drawn {
+    ($part:expr, $draw:expr) => {{
+        let part = $part;
+        #[cfg(feature = "image")]
+        let part = part.drawn($draw);
+        part
+    }};
+}
+
+#[cfg(feature = "day11")]
+const fn blinks(default: i64) -> Param {
+    Param {
+        name: "blinks",
+        description: "How many times the stones blink",
+        default,
+        min: 0,
+        max: u8::MAX as i64,
+    }
+}
+
+#[cfg(feature = "day14")]
+const ROOM_SIZE: [Param; 2] = [
+    Param {
+        name: "width",
+        description: "Width of the room the robots move in",
+        default: 101,
+        min: 3,
+        max: 10_000,
+    },
+    Param {
+        name: "height",
+        description: "Height of the room the robots move in",
+        default: 103,
+        min: 3,
+        max: 10_000,
+    },
+];
+
+#[cfg(feature = "day14")]
+fn room_size(params: &Params) -> (isize, isize) {
+    (params.get("width"), params.get("height"))
+}
+
+/// Every day enabled by its cargo feature, in order.
+pub static DAYS: &[Day] = &[
+    #[cfg(feature = "day1")]
+    Day {
+        day: 1,
+        title: "Historian Hysteria",
+        input: include_str!("day1.input"),
+        validate: crate::day1::validate,
+        inspect: crate::day1::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day1::part_1(input)),
+            part(2, &[], |input, _| crate::day1::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day2")]
+    Day {
+        day: 2,
+        title: "Red-Nosed Reports",
+        input: include_str!("day2.input"),
+        validate: crate::day2::validate,
+        inspect: crate::day2::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day2::part_1(input)),
+            part(2, &[], |input, _| crate::day2::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day3")]
+    Day {
+        day: 3,
+        title: "Mull It Over",
+        input: include_str!("day3.input"),
+        validate: crate::day3::validate,
+        inspect: crate::day3::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day3::part_1(input)),
+            part(2, &[], |input, _| crate::day3::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day4")]
+    Day {
+        day: 4,
+        title: "Ceres Search",
+        input: include_str!("day4.input"),
+        validate: crate::day4::validate,
+        inspect: crate::day4::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day4::part_1(input)),
+            part(2, &[], |input, _| crate::day4::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day5")]
+    Day {
+        day: 5,
+        title: "Print Queue",
+        input: include_str!("day5.input"),
+        validate: crate::day5::validate,
+        inspect: crate::day5::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day5::part_1(input)),
+            part(2, &[], |input, _| crate::day5::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day6")]
+    Day {
+        day: 6,
+        title: "Guard Gallivant",
+        input: include_str!("day6.input"),
+        validate: crate::day6::validate,
+        inspect: crate::day6::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day6::part_1(input))
+                .simulated(|input, _| replay(crate::day6::patrol(input))),
+            part(2, &[], |input, _| crate::day6::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day7")]
+    Day {
+        day: 7,
+        title: "Bridge Repair",
+        input: include_str!("day7.input"),
+        validate: crate::day7::validate,
+        inspect: crate::day7::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day7::part_1(input)),
+            part(2, &[], |input, _| crate::day7::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day8")]
+    Day {
+        day: 8,
+        title: "Resonant Collinearity",
+        input: include_str!("day8.input"),
+        validate: crate::day8::validate,
+        inspect: crate::day8::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day8::part_1(input)),
+            part(2, &[], |input, _| crate::day8::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day9")]
+    Day {
+        day: 9,
+        title: "Disk Fragmenter",
+        input: include_str!("day9.input"),
+        validate: crate::day9::validate,
+        inspect: crate::day9::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day9::part_1(input)),
+            part(2, &[], |input, _| crate::day9::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day10")]
+    Day {
+        day: 10,
+        title: "Hoof It",
+        input: include_str!("day10.input"),
+        validate: crate::day10::validate,
+        inspect: crate::day10::inspect,
+        parts: [
+            drawn!(
+                part(1, &[], |input, _| crate::day10::part_1(input)),
+                |input, _| Some(crate::day10::draw(input))
+            ),
+            drawn!(
+                part(2, &[], |input, _| crate::day10::part_2(input)),
+                |input, _| Some(crate::day10::draw(input))
+            ),
+        ],
+    },
+    #[cfg(feature = "day11")]
+    Day {
+        day: 11,
+        title: "Plutonian Pebbles",
+        input: include_str!("day11.input"),
+        validate: crate::day11::validate,
+        inspect: crate::day11::inspect,
+        parts: [
+            part(1, &[blinks(25)], |input, params| {
+                crate::day11::part_1(input, params.get("blinks"))
+            }),
+            part(2, &[blinks(75)], |input, params| {
+                crate::day11::part_1(input, params.get("blinks"))
+            }),
+        ],
+    },
+    #[cfg(feature = "day12")]
+    Day {
+        day: 12,
+        title: "Garden Groups",
+        input: include_str!("day12.input"),
+        validate: crate::day12::validate,
+        inspect: crate::day12::inspect,
+        parts: [
+            drawn!(
+                part(1, &[], |input, _| crate::day12::part_1(input)),
+                |input, _| Some(crate::day12::draw(input))
+            ),
+            drawn!(
+                part(2, &[], |input, _| crate::day12::part_2(input)),
+                |input, _| Some(crate::day12::draw(input))
+            ),
+        ],
+    },
+    #[cfg(feature = "day13")]
+    Day {
+        day: 13,
+        title: "Claw Contraption",
+        input: include_str!("day13.input"),
+        validate: crate::day13::validate,
+        inspect: crate::day13::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day13::part_1(input)),
+            part(2, &[], |input, _| crate::day13::part_2(input)),
+        ],
+    },
+    #[cfg(feature = "day14")]
+    Day {
+        day: 14,
+        title: "Restroom Redoubt",
+        input: include_str!("day14.input"),
+        validate: crate::day14::validate,
+        inspect: crate::day14::inspect,
+        parts: [
+            drawn!(
+                part(1, &ROOM_SIZE, |input, params| {
+                    crate::day14::part_1(input, room_size(params))
+                })
+                .simulated(|input, params| replay(crate::day14::room(input, room_size(params)))),
+                |input, params| {
+                    let seconds = crate::day14::ITERATIONS as isize;
+                    crate::day14::draw(input, room_size(params), Some(seconds))
+                }
+            ),
+            drawn!(
+                part(2, &ROOM_SIZE, |input, params| {
+                    crate::day14::part_2(input, room_size(params))
+                })
+                .simulated(|input, params| replay(crate::day14::room(input, room_size(params)))),
+                |input, params| crate::day14::draw(input, room_size(params), None)
+            ),
+        ],
+    },
+    #[cfg(feature = "day15")]
+    Day {
+        day: 15,
+        title: "Warehouse Woes",
+        input: include_str!("day15.input"),
+        validate: crate::day15::validate,
+        inspect: crate::day15::inspect,
+        parts: [
+            part(1, &[], |input, _| crate::day15::part_1(input))
+                .simulated(|input, _| replay(crate::day15::warehouse(input, false))),
+            part(2, &[], |input, _| crate::day15::part_2(input))
+                .simulated(|input, _| replay(crate::day15::warehouse(input, true))),
+        ],
+    },
+];
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn days_are_in_order() {
+        assert!(super::DAYS.windows(2).all(|days| days[0].day < days[1].day));
+
+        for day in super::DAYS {
+            assert_eq!(day.parts.each_ref().map(|part| part.part), [1, 2]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "day14")]
+    fn params() {
+        use super::ParamError;
+
+        let part = super::get(14).unwrap().part(1).unwrap();
+
+        let params = part.params([]).unwrap();
+        assert_eq!(params.get::<isize>("width"), 101);
+
+        let params = part.params([("width", "11"), ("height", "7")]).unwrap();
+        assert_eq!(
+            (params.get::<isize>("width"), params.get::<isize>("height")),
+            (11, 7)
+        );
+
+        assert_eq!(
+            part.params([("depth", "3")]),
+            Err(ParamError::Unknown("depth".to_string()))
+        );
+        assert_eq!(
+            part.params([("width", "wide")]),
+            Err(ParamError::Invalid {
+                name: "width".to_string(),
+                value: "wide".to_string()
+            })
+        );
+        assert!(matches!(
+            part.params([("width", "0")]),
+            Err(ParamError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "day11")]
+    fn solve() {
+        let part = super::get(11).unwrap().part(1).unwrap();
+        let params = part.params([("blinks", "6")]).unwrap();
+        assert_eq!(part.solve(Some("125 17"), &params), 22);
+    }
+}
@@ -0,0 +1,66 @@
+//! Entry points for the fuzz targets in `fuzz/`. They live in the crate itself so that the inputs
+//! which once crashed a target can be replayed by `cargo test`, without a nightly toolchain.
+
+use crate::{days, parse::ParseError};
+
+fn validator(day: u8) -> fn(&str) -> Result<(), ParseError> {
+    days::get(day)
+        .unwrap_or_else(|| panic!("No fuzz target for day {day}"))
+        .validate
+}
+
+/// Feeds arbitrary bytes to the parser of `day`, which has to reject them instead of panicking.
+pub fn parse(day: u8, data: &[u8]) {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = validator(day)(input);
+    }
+}
+
+/// Runs both parts of `day` on `input` if the parser accepts it. Meant for small, puzzle-shaped
+/// inputs, as huge numbers would rightfully overflow.
+pub fn solve(day: u8, input: &str) {
+    if validator(day)(input).is_err() {
+        return;
+    }
+
+    let day = days::get(day).expect("the validator exists");
+    for part in &day.parts {
+        let params = match (day.day, part.part) {
+            (11, _) => part.params([("blinks", "5")]),
+            (14, 1) => part.params([("width", "11"), ("height", "7")]),
+            // part 2 gives up on robots which never draw a picture, which random robots rarely do
+            (14, 2) => continue,
+            _ => part.params([]),
+        };
+
+        part.solve(Some(input), &params.expect("the parameters are valid"));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, panic, path::Path};
+
+    /// Replays every input saved in `fuzz/regressions/dayN/`.
+    #[test]
+    fn regressions() {
+        let regressions = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/regressions");
+
+        for day in crate::days::DAYS.iter().map(|day| day.day) {
+            let Ok(entries) = fs::read_dir(regressions.join(format!("day{day}"))) else {
+                continue;
+            };
+
+            for entry in entries {
+                let path = entry.unwrap().path();
+                let data = fs::read(&path).unwrap();
+
+                let replayed = panic::catch_unwind(|| {
+                    super::parse(day, &data);
+                    super::solve(day, &String::from_utf8_lossy(&data));
+                });
+                assert!(replayed.is_ok(), "replaying {} panicked", path.display());
+            }
+        }
+    }
+}
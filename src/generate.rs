@@ -0,0 +1,227 @@
+//! Small, random inputs in the shape of each day's puzzle input: the fuzz targets run the solvers
+//! on them, and `crosscheck` compares the solvers with another implementation on them.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// How much data [random] draws an input from, far more than any of them needs.
+const DATA_SIZE: usize = 4096;
+
+/// The input for `day` drawn from `seed`, always the same one for the same seed.
+///
+/// # Panics
+///
+/// If there's no day `day`.
+pub fn random(day: u8, seed: u64) -> String {
+    // splitmix64, which is plenty for picking inputs
+    let mut state = seed;
+    let data: Vec<u8> = (0..DATA_SIZE / 8)
+        .flat_map(|_| {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            (z ^ (z >> 31)).to_le_bytes()
+        })
+        .collect();
+
+    // running out of data only makes the input smaller
+    input(day, &mut Unstructured::new(&data)).expect("there's enough data")
+}
+
+/// A small input for `day` in the shape of the puzzle's, built out of the data in `u`.
+///
+/// # Panics
+///
+/// If there's no day `day`.
+pub fn input(day: u8, u: &mut Unstructured) -> Result<String> {
+    match day {
+        1 => lines(u, 0..=20, |u| {
+            Ok(format!("{}   {}", u8::arbitrary(u)?, u8::arbitrary(u)?))
+        }),
+        2 => lines(u, 1..=10, |u| {
+            let levels = u.int_in_range(1..=8)?;
+            let report = (0..levels)
+                .map(|_| u8::arbitrary(u).map(|level| level.to_string()))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(report.join(" "))
+        }),
+        3 => {
+            const TOKENS: &[&str] = &["mul(", ")", ",", "do()", "don't()", "1", "23", "456", "x"];
+            let tokens = (0..u.int_in_range(0..=40)?)
+                .map(|_| u.choose(TOKENS).copied())
+                .collect::<Result<Vec<_>>>()?;
+            Ok(tokens.concat())
+        }
+        4 => grid(u, &['X', 'M', 'A', 'S', '.'], 10),
+        5 => day5(u),
+        6 => {
+            let mut cells = grid_cells(u, &['.', '.', '#'], 10)?;
+            let y = u.choose_index(cells.len())?;
+            let x = u.choose_index(cells[y].len())?;
+            cells[y][x] = *u.choose(&['^', 'v', '<', '>'])?;
+            Ok(render(&cells))
+        }
+        7 => lines(u, 1..=8, |u| {
+            let operands = (0..u.int_in_range(1..=6)?)
+                .map(|_| u.int_in_range(1..=99u8).map(|n| n.to_string()))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(format!("{}: {}", u32::arbitrary(u)?, operands.join(" ")))
+        }),
+        8 => grid(u, &['.', '.', '.', 'a', 'A', '0'], 12),
+        9 => {
+            let digits = (0..u.int_in_range(1..=30)?)
+                .map(|_| u.int_in_range(0..=9u8).map(|n| n.to_string()))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(digits.concat())
+        }
+        10 => grid(u, &['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'], 8),
+        11 => {
+            let stones = (0..u.int_in_range(1..=5)?)
+                .map(|_| u16::arbitrary(u).map(|n| n.to_string()))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(stones.join(" "))
+        }
+        12 => grid(u, &['A', 'B', 'C'], 10),
+        13 => {
+            let machines = (0..u.int_in_range(1..=4)?)
+                .map(|_| {
+                    Ok(format!(
+                        "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}",
+                        u.int_in_range(0..=99)?,
+                        u.int_in_range(0..=99)?,
+                        u.int_in_range(0..=99)?,
+                        u.int_in_range(0..=99)?,
+                        u.int_in_range(0..=20_000)?,
+                        u.int_in_range(0..=20_000)?,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(machines.join("\n\n"))
+        }
+        14 => lines(u, 1..=20, |u| {
+            Ok(format!(
+                "p={},{} v={},{}",
+                u.int_in_range(0..=10)?,
+                u.int_in_range(0..=6)?,
+                u.int_in_range(-5..=5)?,
+                u.int_in_range(-5..=5)?,
+            ))
+        }),
+        15 => day15(u),
+        _ => panic!("No generated input for day {day}"),
+    }
+}
+
+fn lines(
+    u: &mut Unstructured,
+    count: std::ops::RangeInclusive<usize>,
+    mut line: impl FnMut(&mut Unstructured) -> Result<String>,
+) -> Result<String> {
+    let lines = (0..u.int_in_range(count)?)
+        .map(|_| line(u))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(lines.join("\n"))
+}
+
+fn grid_cells(u: &mut Unstructured, alphabet: &[char], max_size: usize) -> Result<Vec<Vec<char>>> {
+    let width = u.int_in_range(1..=max_size)?;
+    let height = u.int_in_range(1..=max_size)?;
+
+    (0..height)
+        .map(|_| (0..width).map(|_| u.choose(alphabet).copied()).collect())
+        .collect()
+}
+
+fn grid(u: &mut Unstructured, alphabet: &[char], max_size: usize) -> Result<String> {
+    grid_cells(u, alphabet, max_size).map(|cells| render(&cells))
+}
+
+fn render(cells: &[Vec<char>]) -> String {
+    cells
+        .iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Rules for every pair of pages, taken from a random order, as the puzzle's own inputs have.
+fn day5(u: &mut Unstructured) -> Result<String> {
+    // at least two pages, as there are no rules otherwise
+    let mut pages: Vec<u8> = (10..10 + u.int_in_range(2..=8)?).collect();
+    for i in (1..pages.len()).rev() {
+        pages.swap(i, u.int_in_range(0..=i)?);
+    }
+
+    let rules: Vec<_> = (0..pages.len())
+        .flat_map(|i| (i + 1..pages.len()).map(move |j| (i, j)))
+        .map(|(i, j)| format!("{}|{}", pages[i], pages[j]))
+        .collect();
+
+    let updates = lines(u, 1..=5, |u| {
+        let mut update = pages.clone();
+        for i in (1..update.len()).rev() {
+            update.swap(i, u.int_in_range(0..=i)?);
+        }
+        update.truncate(u.int_in_range(1..=update.len())?);
+
+        Ok(update
+            .iter()
+            .map(|page| page.to_string())
+            .collect::<Vec<_>>()
+            .join(","))
+    })?;
+
+    Ok(format!("{}\n\n{updates}", rules.join("\n")))
+}
+
+/// A walled warehouse with a single robot somewhere inside it.
+fn day15(u: &mut Unstructured) -> Result<String> {
+    let width = u.int_in_range(3..=8)?;
+    let height = u.int_in_range(3..=8)?;
+
+    let mut cells = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                        Ok('#')
+                    } else {
+                        u.choose(&['.', '.', '#', 'O']).copied()
+                    }
+                })
+                .collect()
+        })
+        .collect::<Result<Vec<Vec<_>>>>()?;
+
+    let x = u.int_in_range(1..=width - 2)?;
+    let y = u.int_in_range(1..=height - 2)?;
+    cells[y][x] = '@';
+
+    let moves = (0..u.int_in_range(1..=30)?)
+        .map(|_| u.choose(&['^', 'v', '<', '>']).copied())
+        .collect::<Result<String>>()?;
+
+    Ok(format!("{}\n\n{moves}", render(&cells)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::random;
+    use crate::days;
+
+    #[test]
+    fn valid_inputs() {
+        for day in days::DAYS {
+            for seed in 0..20 {
+                let input = random(day.day, seed);
+                assert_eq!((day.validate)(&input), Ok(()), "day {}:\n{input}", day.day);
+            }
+        }
+    }
+
+    #[test]
+    fn seeds() {
+        assert_eq!(random(2, 7), random(2, 7));
+        assert_ne!(random(2, 7), random(2, 8));
+    }
+}
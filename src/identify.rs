@@ -0,0 +1,157 @@
+//! Guesses which day an input belongs to.
+//!
+//! Each day's parser decides whether the input is valid for it at all. Since many inputs are valid
+//! for several days (any text is a valid memory dump for day 3, any rectangle of characters is a
+//! valid garden for day 12), every accepting day then scores how typical the input's shape is for
+//! it. The scores are scaled down into confidences when several days fit well, but stay low when
+//! no day does.
+
+use crate::{
+    days::{self, Day},
+    parse::normalize,
+};
+
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub day: &'static Day,
+    /// Between 0 and 1, the confidences of all candidates add up to at most 1.
+    pub confidence: f64,
+}
+
+fn lines(input: &str) -> Vec<&str> {
+    input.trim().lines().map(str::trim_end).collect()
+}
+
+fn grid_chars(input: &str) -> impl Iterator<Item = char> + '_ {
+    input.chars().filter(|c| !c.is_whitespace())
+}
+
+fn integer_count(line: &str) -> usize {
+    line.split_whitespace().count()
+}
+
+/// How typical the shape of `input` is for `day`, between 0 and 1, assuming the day's parser
+/// accepts it.
+fn typicality(day: u8, input: &str) -> f64 {
+    let lines = lines(input);
+    let single_line = lines.len() == 1;
+
+    match day {
+        // two columns of location ids
+        1 if single_line => 0.2,
+        1 => f64::from(lines.iter().all(|line| integer_count(line) == 2)),
+        // reports of a handful of levels each
+        2 if single_line => 0.1,
+        2 => f64::from(lines.iter().all(|line| integer_count(line) >= 3)).max(0.1),
+        // anything is a valid memory dump, but real ones contain instructions
+        3 if input.contains("mul(") => 1.0,
+        3 => 0.01,
+        4 => f64::from(grid_chars(input).all(|c| "XMAS".contains(c))),
+        // a map with obstacles, not just any grid containing a `v`
+        6 => f64::from(grid_chars(input).all(|c| ".#^v<>".contains(c))),
+        8 => {
+            let (empty, antennas): (Vec<_>, Vec<_>) = grid_chars(input).partition(|&c| c == '.');
+            let only_antennas = antennas.iter().all(char::is_ascii_alphanumeric);
+            f64::from(only_antennas && empty.len() > antennas.len())
+        }
+        // the disk map is a single (long) line of digits
+        9 => f64::from(single_line),
+        10 if single_line => 0.1,
+        11 if single_line && integer_count(lines[0]) > 1 => 1.0,
+        11 if single_line => 0.2,
+        11 => 0.05,
+        12 if grid_chars(input).all(|c| "XMAS".contains(c)) => 0.3,
+        12 => f64::from(grid_chars(input).all(|c| c.is_ascii_uppercase())),
+        _ => 1.0,
+    }
+}
+
+/// The days `input` might belong to, most likely first.
+pub fn identify(input: &str) -> Vec<Candidate> {
+    // the shapes are judged the way the parsers see the input
+    let input = &*normalize(input);
+
+    // several parsers accept an empty input, which doesn't say anything about the day
+    if input.trim().is_empty() {
+        return vec![];
+    }
+
+    let scores: Vec<_> = days::DAYS
+        .iter()
+        .filter(|day| (day.validate)(input).is_ok())
+        .map(|day| (day, typicality(day.day, input)))
+        .filter(|&(_, score)| score > 0.0)
+        .collect();
+
+    let total = scores.iter().map(|(_, score)| score).sum::<f64>().max(1.0);
+
+    let mut candidates: Vec<_> = scores
+        .into_iter()
+        .map(|(day, score)| Candidate {
+            day,
+            confidence: score / total,
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates
+}
+
+// the tests use the examples of several days
+#[cfg(all(test, feature = "all"))]
+mod test {
+    use std::{fs, path::Path};
+
+    use super::identify;
+
+    #[test]
+    fn puzzle_inputs() {
+        for day in 1..=15 {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("src/day{day}.input"));
+            let input = fs::read_to_string(path).unwrap();
+
+            let candidates = identify(&input);
+            assert_eq!(candidates[0].day.day, day, "{candidates:#?}");
+            assert!(candidates[0].confidence > 0.5, "{candidates:#?}");
+        }
+    }
+
+    #[test]
+    fn examples() {
+        let examples = [
+            ("3   4\n4   3\n2   5\n1   3\n3   9\n3   3", 1),
+            ("7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1", 2),
+            ("47|53\n97|13\n\n75,47,61,53,29\n97,61,53,29,13", 5),
+            ("190: 10 19\n3267: 81 40 27", 7),
+            ("2333133121414131402", 9),
+            ("0123\n1234\n8765\n9876", 10),
+            ("125 17", 11),
+            ("AAAA\nBBCD\nBBCC\nEEEC", 12),
+            ("p=0,4 v=3,-3\np=6,3 v=-1,-3", 14),
+        ];
+
+        for (input, day) in examples {
+            let candidates = identify(input);
+            assert_eq!(candidates[0].day.day, day, "{input}\n{candidates:#?}");
+        }
+    }
+
+    #[test]
+    fn confidences_add_up() {
+        let candidates = identify("3 4\n4 3");
+        assert!(candidates.len() > 1);
+
+        let total: f64 = candidates.iter().map(|c| c.confidence).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn nothing_fits() {
+        assert!(identify(" \n").is_empty());
+
+        let candidates = identify("hello world");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].day.day, 3);
+        assert!(candidates[0].confidence < 0.1);
+    }
+}
@@ -0,0 +1,241 @@
+//! Images of grid states, for grids too large to read as text in a terminal: every cell becomes a
+//! square of pixels whose colour a day picks from a [Palette], e.g. a colour per garden region or a
+//! shade per height. Images are written as PPM, which any image viewer and converter reads, or PNG.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// A colour, as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// Colours to pick from, either in turn for things which are told apart, or blended for
+/// quantities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette(pub &'static [Rgb]);
+
+/// Distinct colours for areas which are told apart from their neighbours, e.g. day 12's regions.
+pub const CATEGORIES: Palette = Palette(&[
+    [0xe6, 0x19, 0x4b],
+    [0x3c, 0xb4, 0x4b],
+    [0xff, 0xe1, 0x19],
+    [0x43, 0x63, 0xd8],
+    [0xf5, 0x82, 0x31],
+    [0x91, 0x1e, 0xb4],
+    [0x42, 0xd4, 0xf4],
+    [0xf0, 0x32, 0xe6],
+    [0xbf, 0xef, 0x45],
+    [0xfa, 0xbe, 0xd4],
+    [0x46, 0x99, 0x90],
+    [0xdc, 0xbe, 0xff],
+    [0x9a, 0x63, 0x24],
+    [0xff, 0xfa, 0xc8],
+    [0x80, 0x00, 0x00],
+    [0xaa, 0xff, 0xc3],
+]);
+
+/// From nothing to a lot, e.g. the number of day 14's robots on a tile.
+pub const HEAT: Palette = Palette(&[
+    [0x0f, 0x0f, 0x23],
+    [0xb0, 0x1e, 0x1e],
+    [0xff, 0xa5, 0x00],
+    [0xff, 0xff, 0xc8],
+]);
+
+/// From low to high ground, e.g. day 10's heights.
+pub const TERRAIN: Palette = Palette(&[
+    [0x1d, 0x4e, 0x2b],
+    [0x6b, 0x8e, 0x23],
+    [0xa0, 0x7a, 0x4b],
+    [0xf5, 0xf5, 0xf5],
+]);
+
+/// The tiles of the simulations, whose recordings give each kind of character a colour, see
+/// `src/record.rs`.
+pub const TILES: Palette = Palette(&[
+    // floor
+    [0x0f, 0x0f, 0x23],
+    // walls and obstacles
+    [0x70, 0x70, 0x80],
+    // boxes
+    [0xc0, 0x8a, 0x3e],
+    // the guard and the robot
+    [0xff, 0xd7, 0x00],
+    // the robots of day 14
+    [0x3c, 0xc8, 0x5a],
+    // anything else
+    [0xcc, 0xcc, 0xcc],
+]);
+
+impl Palette {
+    /// Where `colour` is in the palette, if it's one of its colours.
+    pub fn index(&self, colour: Rgb) -> Option<usize> {
+        self.0.iter().position(|&other| other == colour)
+    }
+
+    /// The colour of category `index`, going round the palette if there are more categories than
+    /// colours.
+    pub fn category(&self, index: usize) -> Rgb {
+        self.0[index % self.0.len()]
+    }
+
+    /// The colour `amount` of the way along the palette, blending its neighbouring colours, where
+    /// `amount` is between 0 and 1.
+    pub fn shade(&self, amount: f64) -> Rgb {
+        let position = amount.clamp(0.0, 1.0) * (self.0.len() - 1) as f64;
+        let low = position.floor() as usize;
+        let high = (low + 1).min(self.0.len() - 1);
+        let blend = position - low as f64;
+
+        let [from, to] = [self.0[low], self.0[high]];
+        [0, 1, 2]
+            .map(|c| (f64::from(from[c]) * (1.0 - blend) + f64::from(to[c]) * blend).round() as u8)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// An image whose pixel `(x, y)` is `colour(x, y)`.
+    pub fn from_fn(width: usize, height: usize, colour: impl Fn(usize, usize) -> Rgb) -> Self {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| colour(x, y))
+            .collect();
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pixels, row by row.
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// This image with every pixel drawn as a `factor` × `factor` square.
+    pub fn scaled(&self, factor: usize) -> Self {
+        Self::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self.pixels[y / factor * self.width + x / factor]
+        })
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.concat()
+    }
+
+    /// Writes the image as a binary PPM.
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.bytes())?;
+        out.flush()
+    }
+
+    pub fn write_png(&self, mut out: impl Write) -> io::Result<()> {
+        let (Ok(width), Ok(height)) = (u32::try_from(self.width), u32::try_from(self.height))
+        else {
+            return Err(io::Error::other("the image is too large for a PNG"));
+        };
+
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.bytes())
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+
+        out.flush()
+    }
+
+    /// Writes the image to `path`, as a PNG if it ends in `.png` and as a PPM otherwise.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        let is_png = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+
+        if is_png {
+            self.write_png(file)
+        } else {
+            self.write_ppm(file)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Image, Palette, CATEGORIES};
+
+    fn checkerboard() -> Image {
+        Image::from_fn(3, 2, |x, y| CATEGORIES.category(x + y))
+    }
+
+    #[test]
+    fn palettes() {
+        let palette = Palette(&[[0, 0, 0], [200, 100, 0], [200, 200, 200]]);
+        assert_eq!(palette.shade(0.0), [0, 0, 0]);
+        assert_eq!(palette.shade(0.25), [100, 50, 0]);
+        assert_eq!(palette.shade(0.5), [200, 100, 0]);
+        assert_eq!(palette.shade(1.0), [200, 200, 200]);
+        assert_eq!(palette.shade(7.0), [200, 200, 200]);
+
+        assert_eq!(palette.category(1), palette.category(4));
+        assert_eq!(palette.index([200, 100, 0]), Some(1));
+        assert_eq!(palette.index([1, 2, 3]), None);
+    }
+
+    #[test]
+    fn scaling() {
+        let image = checkerboard().scaled(2);
+        assert_eq!((image.width(), image.height()), (6, 4));
+        assert_eq!(image.get(5, 3), checkerboard().get(2, 1));
+        assert_eq!(image.get(6, 0), None);
+    }
+
+    #[test]
+    fn ppm() {
+        let mut ppm = vec![];
+        checkerboard().write_ppm(&mut ppm).unwrap();
+
+        assert!(ppm.starts_with(b"P6\n3 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 3 * 2 * 3);
+        assert_eq!(ppm[11..14], CATEGORIES.category(0));
+    }
+
+    #[test]
+    fn png() {
+        let mut png = vec![];
+        checkerboard().write_png(&mut png).unwrap();
+
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(pixels[..info.buffer_size()], checkerboard().bytes());
+    }
+}
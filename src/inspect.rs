@@ -0,0 +1,176 @@
+//! Statistics about the shape of an input, e.g. the size of a grid or how many robots there are,
+//! which every day gathers from its parser before anything is solved.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Count(usize),
+    Size {
+        width: usize,
+        height: usize,
+    },
+    /// How many times each item occurs, in the order of the items.
+    Histogram(Vec<(String, usize)>),
+}
+
+/// The statistics of an input, in the order the day added them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats(Vec<(&'static str, Value)>);
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn count(mut self, name: &'static str, count: usize) -> Self {
+        self.0.push((name, Value::Count(count)));
+        self
+    }
+
+    pub fn size(mut self, name: &'static str, width: usize, height: usize) -> Self {
+        self.0.push((name, Value::Size { width, height }));
+        self
+    }
+
+    /// How many times each of `items` occurs.
+    pub fn histogram<T: Ord + Display>(
+        mut self,
+        name: &'static str,
+        items: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let counts = items.into_iter().fold(BTreeMap::new(), |mut counts, item| {
+            *counts.entry(item).or_insert(0) += 1;
+            counts
+        });
+        let counts = counts
+            .into_iter()
+            .map(|(item, count)| (item.to_string(), count))
+            .collect();
+
+        self.0.push((name, Value::Histogram(counts)));
+        self
+    }
+
+    /// The size of `grid` and how many times each character occurs in it.
+    pub fn grid(self, grid: &[Vec<char>]) -> Self {
+        let width = grid.first().map_or(0, Vec::len);
+        self.size("size", width, grid.len())
+            .histogram("characters", grid.iter().flatten())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0
+            .iter()
+            .find(|(stat, _)| *stat == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.0 {
+            match value {
+                Value::Count(count) => writeln!(f, "{name}: {count}")?,
+                Value::Size { width, height } => writeln!(f, "{name}: {width} × {height}")?,
+                Value::Histogram(counts) => {
+                    writeln!(f, "{name}:")?;
+                    let width = counts.iter().map(|(item, _)| item.len()).max().unwrap_or(0);
+                    for (item, count) in counts {
+                        writeln!(f, "  {item:<width$}  {count}")?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Count(count) => count.serialize(serializer),
+            Self::Size { width, height } => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("width", width)?;
+                map.serialize_entry("height", height)?;
+                map.end()
+            }
+            Self::Histogram(counts) => {
+                let mut map = serializer.serialize_map(Some(counts.len()))?;
+                for (item, count) in counts {
+                    map.serialize_entry(item, count)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// An object of the statistics by name, in the order the day added them.
+impl Serialize for Stats {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, value) in &self.0 {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Stats, Value};
+
+    fn stats() -> Stats {
+        let grid = ["#.#", "..@"].map(|row| row.chars().collect());
+        Stats::new()
+            .grid(&grid)
+            .count("moves", 12)
+            .histogram("lengths", [10, 9, 10])
+    }
+
+    #[test]
+    fn gathering() {
+        let stats = stats();
+        assert_eq!(
+            stats.get("size"),
+            Some(&Value::Size {
+                width: 3,
+                height: 2
+            })
+        );
+        assert_eq!(stats.get("moves"), Some(&Value::Count(12)));
+        // in the order of the numbers, not of their text
+        assert_eq!(
+            stats.get("lengths"),
+            Some(&Value::Histogram(vec![
+                ("9".to_string(), 1),
+                ("10".to_string(), 2)
+            ]))
+        );
+        assert_eq!(stats.get("robots"), None);
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            stats().to_string(),
+            "size: 3 × 2\ncharacters:\n  #  2\n  .  3\n  @  1\nmoves: 12\nlengths:\n  9   1\n  10  2\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            serde_json::to_string(&stats()).unwrap(),
+            r##"{"size":{"width":3,"height":2},"characters":{"#":2,".":3,"@":1},"moves":12,"lengths":{"9":1,"10":2}}"##
+        );
+    }
+}
@@ -0,0 +1,69 @@
+pub mod answer;
+#[cfg(feature = "cli")]
+pub mod batch;
+pub mod bitgrid;
+#[cfg(feature = "cli")]
+pub mod cache;
+pub mod cancel;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "generate")]
+pub mod crosscheck;
+#[cfg(feature = "dashboard")]
+pub mod dashboard;
+#[cfg(feature = "day1")]
+pub mod day1;
+#[cfg(feature = "day10")]
+pub mod day10;
+#[cfg(feature = "day11")]
+pub mod day11;
+#[cfg(feature = "day12")]
+pub mod day12;
+#[cfg(feature = "day13")]
+pub mod day13;
+#[cfg(feature = "day14")]
+pub mod day14;
+#[cfg(feature = "day15")]
+pub mod day15;
+#[cfg(feature = "day2")]
+pub mod day2;
+#[cfg(feature = "day3")]
+pub mod day3;
+#[cfg(feature = "day4")]
+pub mod day4;
+#[cfg(feature = "day5")]
+pub mod day5;
+#[cfg(feature = "day6")]
+pub mod day6;
+#[cfg(feature = "day7")]
+pub mod day7;
+#[cfg(feature = "day8")]
+pub mod day8;
+#[cfg(feature = "day9")]
+pub mod day9;
+pub mod days;
+pub mod fuzzing;
+#[cfg(feature = "generate")]
+pub mod generate;
+#[cfg(feature = "cli")]
+pub mod identify;
+#[cfg(feature = "image")]
+pub mod image;
+pub mod inspect;
+pub mod math;
+pub mod parse;
+pub mod progress;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "record")]
+pub mod record;
+#[cfg(feature = "cli")]
+pub mod report;
+pub mod search;
+#[cfg(feature = "cli")]
+pub mod serve;
+pub mod simulation;
+// unused if none of the days with snapshots is enabled
+#[cfg(test)]
+#[allow(unused)]
+mod snapshot;
@@ -1,66 +1,1053 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-use clap::Parser;
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+    time::Duration,
+};
+#[cfg(feature = "generate")]
+use std::{panic, time::SystemTime};
+
+#[cfg(feature = "dashboard")]
+use advent_of_code_2024::dashboard::Dashboard;
+#[cfg(feature = "record")]
+use advent_of_code_2024::record;
+use advent_of_code_2024::{
+    batch::{self, Matrix},
+    cache::{self, Cache},
+    config::{self, Config},
+    days::{self, Day, ParamError, Params},
+    identify::identify,
+    progress::{Bar, Tracker},
+    report,
+    serve::Service,
+    simulation::Player,
+};
+#[cfg(feature = "generate")]
+use advent_of_code_2024::{
+    crosscheck::{Outcome, Reference},
+    generate,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::json;
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// The advent of code day to run
-    #[arg(short, long)]
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The advent of code day to run, see `list` for the available days
+    #[arg(short, long, required = true, value_parser = parse_day)]
+    day: Option<u8>,
+
+    /// The part of the advent of code day to run, both parts if left out
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Set a parameter of the day, e.g. `blinks=30`, see `list` for the parameters
+    #[arg(short = 'P', long = "param", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+
+    /// Stop the solution after this many seconds
+    #[arg(short, long, value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+
+    /// How to print the results [default: text]
+    #[arg(short, long, global = true, value_enum)]
+    format: Option<Format>,
+
+    /// Run the solutions even if the answer cache aoc.toml turns on has their answers
+    #[arg(long, global = true)]
+    no_cache: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the available days and their parts
+    List,
+    /// Serve the solutions over HTTP, see `src/serve.rs` for the endpoints
+    Serve {
+        /// The address to listen on
+        #[arg(short, long, default_value = "127.0.0.1:2024")]
+        address: String,
+
+        /// Stop solutions after this many seconds [default: 60]
+        #[arg(short, long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Browse the days and run them in a full-screen dashboard, with the users' inputs to switch to
+    #[cfg(feature = "dashboard")]
+    Dashboard {
+        /// The directory containing a directory of inputs per user [default: inputs]
+        #[arg(short, long)]
+        inputs: Option<PathBuf>,
+
+        /// Stop solutions after this many seconds [default: 60]
+        #[arg(short, long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Run every day for every user in the inputs directory, see `src/batch.rs` for the layout
+    Batch {
+        /// The directory containing a directory of inputs per user [default: inputs]
+        #[arg(short, long)]
+        inputs: Option<PathBuf>,
+
+        /// Stop solutions after this many seconds [default: 60]
+        #[arg(short, long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Write a table of every day's answers and runtimes
+    Report {
+        /// Write HTML instead of Markdown
+        #[arg(long)]
+        html: bool,
+
+        /// Leave the answers out
+        #[arg(long)]
+        redact: bool,
+
+        /// The file to write, instead of stdout
+        #[arg(short, long, conflicts_with = "readme")]
+        output: Option<PathBuf>,
+
+        /// Update the table in the project's README.md instead, the one next to aoc.toml or else
+        /// in the working directory
+        #[arg(long, conflicts_with = "html")]
+        readme: bool,
+
+        /// Stop solutions after this many seconds [default: 60]
+        #[arg(short, long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Guess which day an input file belongs to
+    Identify {
+        /// The input file
+        file: PathBuf,
+    },
+    /// Show the shape of a day's input, e.g. the size of the grid or the number of robots
+    Inspect {
+        /// The day whose input it is
+        #[arg(short, long, value_parser = parse_day)]
+        day: u8,
+
+        /// The input file, instead of the bundled input
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Show a day's simulation after some steps, e.g. the warehouse of day 15 after move 137
+    Simulate {
+        /// The day to simulate, days 6, 14 and 15 are simulations
+        #[arg(short, long, value_parser = parse_day)]
+        day: u8,
+
+        /// The part to simulate
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// The step to show, the last one if the simulation ends before it. Recordings stop there
+        /// [default: 0, or the end of the simulation when recording]
+        #[arg(short, long)]
+        step: Option<usize>,
+
+        /// Set a parameter of the day, e.g. `width=11`
+        #[arg(short = 'P', long = "param", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+
+        /// The input file, instead of the bundled input
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        #[cfg(feature = "record")]
+        #[command(flatten)]
+        recording: Recording,
+    },
+    /// Draw a day's grid as an image, e.g. the regions of day 12's garden
+    #[cfg(feature = "image")]
+    Draw {
+        /// The day to draw, days 10, 12 and 14 have pictures
+        #[arg(short, long, value_parser = parse_day)]
+        day: u8,
+
+        /// The part to draw
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Set a parameter of the day, e.g. `width=11`
+        #[arg(short = 'P', long = "param", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+
+        /// The input file, instead of the bundled input
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        #[command(flatten)]
+        drawing: Drawing,
+    },
+    /// Compare the answers with another solver's, on an input or on generated ones
+    #[cfg(feature = "generate")]
+    Crosscheck {
+        /// The day to compare
+        #[arg(short, long, value_parser = parse_day)]
+        day: u8,
+
+        /// The part to compare, both parts if left out
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// The other solver, a shell command printing the answer of part `{part}` of day `{day}`
+        /// for the input in the file `{input}`, or on stdin if there's no `{input}`
+        #[arg(long, value_name = "COMMAND")]
+        cmd: String,
+
+        /// Set a parameter of the day, e.g. `width=11`
+        #[arg(short = 'P', long = "param", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+
+        /// The input file, instead of the bundled input
+        #[arg(short, long, conflicts_with = "generate")]
+        input: Option<PathBuf>,
+
+        /// Compare on this many generated inputs instead, stopping at the first disagreement
+        #[arg(short, long, value_name = "COUNT")]
+        generate: Option<u64>,
+
+        /// The seed of the first generated input [default: random]
+        #[arg(long, requires = "generate")]
+        seed: Option<u64>,
+
+        /// Stop either solver after this many seconds [default: 60]
+        #[arg(short, long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Manage the answer cache, see `cache` in aoc.toml
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+}
+
+#[cfg(feature = "record")]
+#[derive(clap::Args)]
+struct Recording {
+    /// Record the steps up to `--step` as an asciinema cast
+    #[arg(long, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// Record the steps up to `--step` as an animated GIF
+    #[arg(long, value_name = "FILE")]
+    gif: Option<PathBuf>,
+
+    /// Record every this many steps
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    every: u64,
+
+    /// The frames a second of the recordings
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..=100))]
+    fps: u32,
+}
+
+#[cfg(feature = "image")]
+#[derive(clap::Args)]
+struct Drawing {
+    /// The image to write, a PNG if it ends in `.png` and a PPM otherwise
+    #[arg(short, long, value_name = "FILE")]
+    output: PathBuf,
+
+    /// The width and height of a cell of the grid, in pixels
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=64))]
+    scale: u8,
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Remove every cached answer
+    Clear,
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    let day: u8 = day.parse().map_err(|err| format!("{err}"))?;
+    match days::get(day) {
+        Some(_) => Ok(day),
+        None if (1..=25).contains(&day) => Err(format!(
+            "day {day} isn't available, see `list` for the days which are"
+        )),
+        None => Err("advent of code only has days 1 to 25".to_string()),
+    }
+}
+
+fn parse_param(param: &str) -> Result<(String, String), String> {
+    let (name, value) = param
+        .split_once('=')
+        .ok_or_else(|| format!("{param:?} isn't of the form name=value"))?;
+    Ok((name.to_string(), value.to_string()))
+}
+
+fn parse_seconds(seconds: &str) -> Result<Duration, String> {
+    let seconds: f64 = seconds.parse().map_err(|err| format!("{err}"))?;
+    Duration::try_from_secs_f64(seconds).map_err(|err| format!("{err}"))
+}
+
+/// How long the subcommands give each solution, unless told otherwise.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub fn main() -> ExitCode {
+    let args = Args::parse();
+    let config = match config::find() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let format = args.format.unwrap_or(match config.format {
+        Some(config::Format::Json) => Format::Json,
+        Some(config::Format::Text) | None => Format::Text,
+    });
+    let timeout = args.timeout.or(config.timeout());
+    let cache = config
+        .cache
+        .as_ref()
+        .filter(|_| !args.no_cache)
+        .map(Cache::new);
+    let cache = cache.as_ref();
+    let subcommand_timeout =
+        |timeout: Option<Duration>| timeout.or(config.timeout()).unwrap_or(DEFAULT_TIMEOUT);
+
+    match args.command {
+        Some(Command::List) => list(format),
+        Some(Command::Serve { address, timeout }) => serve(&address, subcommand_timeout(timeout)),
+        #[cfg(feature = "dashboard")]
+        Some(Command::Dashboard { inputs, timeout }) => {
+            let inputs = inputs
+                .or(config.inputs.clone())
+                .unwrap_or_else(|| PathBuf::from("inputs"));
+            let timeout = subcommand_timeout(timeout);
+            show_dashboard(config.clone(), &inputs, timeout, cache.cloned())
+        }
+        Some(Command::Batch { inputs, timeout }) => {
+            let inputs = inputs
+                .or(config.inputs.clone())
+                .unwrap_or_else(|| PathBuf::from("inputs"));
+            run_batch(&inputs, subcommand_timeout(timeout), cache, format)
+        }
+        Some(Command::Report {
+            html,
+            redact,
+            output,
+            readme,
+            timeout,
+        }) => write_report(
+            html,
+            redact,
+            output.as_deref(),
+            readme,
+            config.year.unwrap_or(report::YEAR),
+            subcommand_timeout(timeout),
+            cache,
+        ),
+        Some(Command::Identify { file }) => identify_file(&file),
+        Some(Command::Inspect { day, input }) => inspect(day, input.as_deref(), format),
+        Some(Command::Simulate {
+            day,
+            part,
+            step,
+            params,
+            input,
+            #[cfg(feature = "record")]
+            recording,
+        }) => match simulation(day, part, &config, &params, input.as_deref()) {
+            #[cfg(feature = "record")]
+            Ok(simulation) if recording.record.is_some() || recording.gif.is_some() => {
+                record(day, part, simulation, step, &recording)
+            }
+            Ok(simulation) => show(day, part, simulation, step.unwrap_or(0), format),
+            Err(code) => code,
+        },
+        #[cfg(feature = "image")]
+        Some(Command::Draw {
+            day,
+            part,
+            params,
+            input,
+            drawing,
+        }) => draw(day, part, &config, &params, input.as_deref(), &drawing),
+        #[cfg(feature = "generate")]
+        Some(Command::Crosscheck {
+            day,
+            part,
+            cmd,
+            params,
+            input,
+            generate,
+            seed,
+            timeout,
+        }) => {
+            let timeout = subcommand_timeout(timeout);
+            let reference = Reference::new(cmd).with_timeout(Some(timeout));
+            let inputs = match generate {
+                Some(count) => Inputs::Generated { count, seed },
+                None => Inputs::Given(input),
+            };
+            crosscheck(day, part, &config, &params, &reference, inputs, timeout)
+        }
+        Some(Command::Cache {
+            command: CacheCommand::Clear,
+        }) => clear_cache(&config),
+        None => {
+            let day = days::get(args.day.unwrap()).expect("the day was checked when parsing it");
+            let declared = |name: &str| {
+                day.parts
+                    .iter()
+                    .any(|part| part.params.iter().any(|param| param.name == name))
+            };
+            if let Some((name, _)) = args.params.iter().find(|(name, _)| !declared(name)) {
+                eprintln!("day {} has no parameter {name:?}", day.day);
+                return ExitCode::from(2);
+            }
+
+            let parts = match args.part {
+                Some(part) => vec![day
+                    .part(part)
+                    .expect("the part was checked when parsing it")],
+                None => day.parts.iter().collect(),
+            };
+
+            // run every part even if one fails, but still report the failure
+            let codes: Vec<_> = parts
+                .into_iter()
+                .map(|part| run(day, part, &config, &args.params, timeout, cache, format))
+                .collect();
+            codes
+                .into_iter()
+                .find(|&code| code != ExitCode::SUCCESS)
+                .unwrap_or(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn list(format: Format) -> ExitCode {
+    if format == Format::Json {
+        println!("{}", json!(days::DAYS));
+        return ExitCode::SUCCESS;
+    }
+
+    for day in days::DAYS {
+        let parts: Vec<_> = day
+            .parts
+            .iter()
+            .map(|part| {
+                let params: Vec<_> = part
+                    .params
+                    .iter()
+                    .map(|param| format!("{} = {}", param.name, param.default))
+                    .collect();
+
+                if params.is_empty() {
+                    part.part.to_string()
+                } else {
+                    format!("{} ({})", part.part, params.join(", "))
+                }
+            })
+            .collect();
+
+        println!(
+            "day {:>2}  {:<24}  parts {}",
+            day.day,
+            day.title,
+            parts.join(", ")
+        );
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn run(
+    day: &Day,
+    solution: &days::Part,
+    config: &Config,
+    params: &[(String, String)],
+    timeout: Option<Duration>,
+    cache: Option<&Cache>,
+    format: Format,
+) -> ExitCode {
+    let part = solution.part;
+    let params = match resolve_params(day, solution, config, params) {
+        Ok(params) => params,
+        Err(err) => {
+            eprintln!("day {}, part {part}: {err}", day.day);
+            return ExitCode::from(2);
+        }
+    };
+    // the progress bar would only get in the way of whatever reads the output
+    let tracker = Arc::new(Tracker::default());
+    let bar =
+        (format == Format::Text && io::stdout().is_terminal()).then(|| Bar::show(tracker.clone()));
+
+    let result = cache::run(cache, day, solution, None, &params, timeout, Some(tracker));
+    drop(bar);
+
+    let day = day.day;
+    match (result, format) {
+        (Ok(run), Format::Text) => {
+            println!("day {day}, part {part}: {}", run.answer);
+            ExitCode::SUCCESS
+        }
+        (Ok(run), Format::Json) => {
+            let duration_ms = run.duration.as_secs_f64() * 1000.0;
+            let answer = run.answer;
+            println!(
+                "{}",
+                json!({ "day": day, "part": part, "answer": answer, "duration_ms": duration_ms })
+            );
+            ExitCode::SUCCESS
+        }
+        (Err(failure), Format::Text) => {
+            eprintln!("day {day}, part {part}: {failure}");
+            ExitCode::FAILURE
+        }
+        (Err(failure), Format::Json) => {
+            let error = failure.to_string();
+            println!("{}", json!({ "day": day, "part": part, "error": error }));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The parameters of `solution`, from the config and then the flags, which take precedence. The
+/// flags only apply to the parts which have the parameter.
+fn resolve_params(
+    day: &Day,
+    solution: &days::Part,
+    config: &Config,
+    params: &[(String, String)],
+) -> Result<Params, ParamError> {
+    let config_values = config.param_values(day.day, solution);
+    let values = config_values
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .chain(
+            params
+                .iter()
+                .filter(|(name, _)| solution.params.iter().any(|param| param.name == name))
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        );
+
+    solution.params(values)
+}
+
+/// Part `part` of `day` with its parameters and the input read from `input`, which is checked, or
+/// the exit code if any of them is wrong.
+fn prepare(
     day: u8,
+    part: u8,
+    config: &Config,
+    params: &[(String, String)],
+    input: Option<&Path>,
+) -> Result<(&'static days::Part, Params, Option<String>), ExitCode> {
+    let day = days::get(day).expect("the day was checked when parsing it");
+    let solution = day
+        .part(part)
+        .expect("the part was checked when parsing it");
 
-    /// The part of the advent of code day to run
-    #[arg(short, long)]
+    if let Some((name, _)) = params
+        .iter()
+        .find(|(name, _)| !solution.params.iter().any(|param| param.name == name))
+    {
+        eprintln!("day {}, part {part} has no parameter {name:?}", day.day);
+        return Err(ExitCode::from(2));
+    }
+    let params = resolve_params(day, solution, config, params).map_err(|err| {
+        eprintln!("day {}, part {part}: {err}", day.day);
+        ExitCode::from(2)
+    })?;
+
+    let input = input.map(fs::read_to_string).transpose().map_err(|err| {
+        eprintln!("couldn't read the input: {err}");
+        ExitCode::FAILURE
+    })?;
+    if let Err(err) = (day.validate)(input.as_deref().unwrap_or(day.input)) {
+        eprintln!("invalid input for day {}: {err}", day.day);
+        return Err(ExitCode::FAILURE);
+    }
+
+    Ok((solution, params, input))
+}
+
+/// The simulation of `part` of `day`, or the exit code if there's none.
+fn simulation(
+    day: u8,
+    part: u8,
+    config: &Config,
+    params: &[(String, String)],
+    input: Option<&Path>,
+) -> Result<Box<dyn Player>, ExitCode> {
+    let (solution, params, input) = prepare(day, part, config, params, input)?;
+
+    solution
+        .simulation(input.as_deref(), &params)
+        .ok_or_else(|| {
+            eprintln!("day {day}, part {part} isn't a simulation");
+            ExitCode::from(2)
+        })
+}
+
+#[cfg(feature = "image")]
+fn draw(
+    day: u8,
+    part: u8,
+    config: &Config,
+    params: &[(String, String)],
+    input: Option<&Path>,
+    drawing: &Drawing,
+) -> ExitCode {
+    let (solution, params, input) = match prepare(day, part, config, params, input) {
+        Ok(prepared) => prepared,
+        Err(code) => return code,
+    };
+
+    let Some(image) = solution.image(input.as_deref(), &params) else {
+        eprintln!("day {day}, part {part} has no picture");
+        return ExitCode::from(2);
+    };
+    let image = image.scaled(usize::from(drawing.scale));
+
+    let output = &drawing.output;
+    match image.save(output) {
+        Ok(()) => {
+            println!(
+                "drew day {day}, part {part} to {} ({}×{})",
+                output.display(),
+                image.width(),
+                image.height()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("couldn't write {}: {err}", output.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn show(
+    day: u8,
+    part: u8,
+    mut simulation: Box<dyn Player>,
+    step: usize,
+    format: Format,
+) -> ExitCode {
+    let reached = simulation.seek(step);
+    let state = simulation.render();
+    let done = simulation.is_done();
+
+    match format {
+        Format::Text if reached < step => {
+            println!("day {day}, part {part} ended after step {reached}:\n{state}")
+        }
+        Format::Text => println!("day {day}, part {part} after step {reached}:\n{state}"),
+        Format::Json => println!(
+            "{}",
+            json!({ "day": day, "part": part, "step": reached, "done": done, "state": state })
+        ),
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(feature = "record")]
+fn record(
+    day: u8,
     part: u8,
+    mut simulation: Box<dyn Player>,
+    step: Option<usize>,
+    recording: &Recording,
+) -> ExitCode {
+    let every = usize::try_from(recording.every).unwrap_or(usize::MAX);
+    let frames = record::frames(&mut *simulation, step.unwrap_or(usize::MAX), every);
+    let last = frames.last().expect("there's always a first frame").step;
+    if step.is_none() && frames.len() == record::MAX_FRAMES && !simulation.is_done() {
+        eprintln!(
+            "stopped after {} frames, pass --step to record more",
+            record::MAX_FRAMES
+        );
+    }
+
+    let title = format!("day {day}, part {part}");
+    let recordings = [
+        recording.record.as_deref().map(|path| {
+            let file = fs::File::create(path).map(io::BufWriter::new);
+            (
+                path,
+                file.and_then(|file| record::write_cast(file, &frames, recording.fps, &title)),
+            )
+        }),
+        recording.gif.as_deref().map(|path| {
+            let file = fs::File::create(path).map(io::BufWriter::new);
+            (
+                path,
+                file.and_then(|file| record::write_gif(file, &frames, recording.fps)),
+            )
+        }),
+    ];
+
+    for (path, result) in recordings.into_iter().flatten() {
+        if let Err(err) = result {
+            eprintln!("couldn't write {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+
+        println!(
+            "recorded steps 0 to {last} of {title} to {} ({} frames)",
+            path.display(),
+            frames.len()
+        );
+    }
+
+    ExitCode::SUCCESS
 }
 
-pub fn main() {
-    let Args { day, part } = Args::parse();
-
-    match (day, part) {
-        (1, 1) => println!("day 1, part 1: {}", day1::part_1(None)),
-        (1, 2) => println!("day 1, part 2: {}", day1::part_2(None)),
-        (2, 1) => println!("day 2, part 1: {}", day2::part_1(None)),
-        (2, 2) => println!("day 2, part 2: {}", day2::part_2(None)),
-        (3, 1) => println!("day 3, part 1: {}", day3::part_1(None)),
-        (3, 2) => println!("day 3, part 2: {}", day3::part_2(None)),
-        (4, 1) => println!("day 4, part 1: {}", day4::part_1(None)),
-        (4, 2) => println!("day 4, part 2: {}", day4::part_2(None)),
-        (5, 1) => println!("day 5, part 1: {}", day5::part_1(None)),
-        (5, 2) => println!("day 5, part 2: {}", day5::part_2(None)),
-        (6, 1) => println!("day 6, part 1: {}", day6::part_1(None)),
-        (6, 2) => println!("day 6, part 2: {}", day6::part_2(None)),
-        (7, 1) => println!("day 7, part 1: {}", day7::part_1(None)),
-        (7, 2) => println!("day 7, part 2: {}", day7::part_2(None)),
-        (8, 1) => println!("day 8, part 1: {}", day8::part_1(None)),
-        (8, 2) => println!("day 8, part 2: {}", day8::part_2(None)),
-        (9, 1) => println!("day 9, part 1: {}", day9::part_1(None)),
-        (9, 2) => println!("day 9, part 2: {}", day9::part_2(None)),
-        (10, 1) => println!("day 10, part 1: {}", day10::part_1(None)),
-        (10, 2) => println!("day 10, part 2: {}", day10::part_2(None)),
-        (11, 1) => println!("day 11, part 1: {}", day11::part_1(None, 25)),
-        (11, 2) => println!("day 11, part 2: {}", day11::part_1(None, 75)),
-        (12, 1) => println!("day 12, part 1: {}", day12::part_1(None)),
-        (12, 2) => println!("day 12, part 2: {}", day12::part_2(None)),
-        (13, 1) => println!("day 13, part 1: {}", day13::part_1(None)),
-        (13, 2) => println!("day 13, part 2: {}", day13::part_2(None)),
-        (14, 1) => println!("day 14, part 1: {}", day14::part_1(None, (101, 103))),
-        (14, 2) => println!("day 14, part 2: {:?}", day14::part_2(None, (101, 103))),
-        (15, 1) => println!("day 15, part 1: {}", day15::part_1(None)),
-        (15, 2) => println!("day 15, part 2: {}", day15::part_2(None)),
-        _ => panic!("Invalid day or part"),
+/// What `crosscheck` compares the solvers on.
+#[cfg(feature = "generate")]
+enum Inputs {
+    /// The input file, or the bundled input.
+    Given(Option<PathBuf>),
+    Generated {
+        count: u64,
+        seed: Option<u64>,
+    },
+}
+
+#[cfg(feature = "generate")]
+fn crosscheck(
+    day: u8,
+    part: Option<u8>,
+    config: &Config,
+    params: &[(String, String)],
+    reference: &Reference,
+    inputs: Inputs,
+    timeout: Duration,
+) -> ExitCode {
+    let day = days::get(day).expect("the day was checked when parsing it");
+    let declared = |name: &str| {
+        day.parts
+            .iter()
+            .any(|part| part.params.iter().any(|param| param.name == name))
+    };
+    if let Some((name, _)) = params.iter().find(|(name, _)| !declared(name)) {
+        eprintln!("day {} has no parameter {name:?}", day.day);
+        return ExitCode::from(2);
+    }
+
+    let mut parts = vec![];
+    for solution in &day.parts {
+        if part.is_some_and(|part| part != solution.part) {
+            continue;
+        }
+
+        match resolve_params(day, solution, config, params) {
+            Ok(params) => parts.push((solution, params)),
+            Err(err) => {
+                eprintln!("day {}, part {}: {err}", day.day, solution.part);
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let (count, seed) = match inputs {
+        Inputs::Given(input) => {
+            let input = match input.map(fs::read_to_string).transpose() {
+                Ok(input) => input.unwrap_or_else(|| day.input.to_string()),
+                Err(err) => {
+                    eprintln!("couldn't read the input: {err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if let Err(err) = (day.validate)(&input) {
+                eprintln!("invalid input for day {}: {err}", day.day);
+                return ExitCode::FAILURE;
+            }
+
+            let mut code = ExitCode::SUCCESS;
+            for (solution, params) in &parts {
+                let outcome = reference.compare(day, solution, params, &input, Some(timeout));
+                println!("day {}, part {}: {outcome}", day.day, solution.part);
+                if !matches!(outcome, Outcome::Agree(_)) {
+                    code = ExitCode::FAILURE;
+                }
+            }
+
+            return code;
+        }
+        Inputs::Generated { count, seed } => (
+            count,
+            seed.unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map_or(0, |time| time.as_nanos() as u64)
+            }),
+        ),
+    };
+
+    // inputs our own solution gives up on, e.g. robots which never draw a picture, are skipped, and
+    // the messages of its panics would drown out the rest while the generated inputs run
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut skipped = 0;
+    let disagreed = 'inputs: {
+        for index in 0..count {
+            let input_seed = seed.wrapping_add(index);
+            let input = generate::random(day.day, input_seed);
+
+            for (solution, params) in &parts {
+                let outcome = reference.compare(day, solution, params, &input, Some(timeout));
+                match outcome {
+                    Outcome::Agree(_) => continue,
+                    Outcome::OursFailed(_) => {
+                        skipped += 1;
+                        continue;
+                    }
+                    Outcome::Differ { .. } | Outcome::TheirsFailed(_) => {}
+                }
+
+                println!(
+                    "day {}, part {}: {outcome} on generated input {} of {count}, which \
+                     `--generate 1 --seed {input_seed}` repeats",
+                    day.day,
+                    solution.part,
+                    index + 1,
+                );
+                let smaller = reference.shrink(day, solution, params, &input, Some(timeout));
+                let outcome = reference.compare(day, solution, params, &smaller, Some(timeout));
+                println!(
+                    "the smallest input found which they disagree on, where {outcome}:\n{smaller}"
+                );
+                break 'inputs true;
+            }
+        }
+
+        false
+    };
+    panic::set_hook(hook);
+
+    if disagreed {
+        return ExitCode::FAILURE;
+    }
+
+    println!(
+        "day {}: both agree on {count} generated inputs from seed {seed}, except for {skipped} \
+         parts ours gave up on",
+        day.day
+    );
+    ExitCode::SUCCESS
+}
+
+fn clear_cache(config: &Config) -> ExitCode {
+    let Some(dir) = &config.cache else {
+        eprintln!("the answer cache is off, set `cache` in aoc.toml to turn it on");
+        return ExitCode::SUCCESS;
+    };
+
+    match Cache::new(dir).clear() {
+        Ok(removed) => {
+            println!("removed {removed} answers from {}", dir.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("couldn't clear {}: {err}", dir.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn serve(address: &str, timeout: Duration) -> ExitCode {
+    let service = match Service::bind(address) {
+        Ok(service) => service.with_timeout(Some(timeout)),
+        Err(err) => {
+            eprintln!("couldn't listen on {address}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(addr) = service.local_addr() {
+        println!("listening on http://{addr}");
+    }
+    service.run();
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(feature = "dashboard")]
+fn show_dashboard(
+    config: Config,
+    inputs: &Path,
+    timeout: Duration,
+    cache: Option<Cache>,
+) -> ExitCode {
+    let users = match batch::load_users(inputs) {
+        Ok(users) => users,
+        // the bundled inputs are enough
+        Err(err) if err.kind() == io::ErrorKind::NotFound => vec![],
+        Err(err) => {
+            eprintln!("couldn't load the inputs in {}: {err}", inputs.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match Dashboard::new(config, users, cache, Some(timeout)).run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("couldn't show the dashboard: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn identify_file(file: &Path) -> ExitCode {
+    let input = match fs::read_to_string(file) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("couldn't read {}: {err}", file.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let candidates = identify(&input);
+    if candidates.is_empty() {
+        eprintln!("{} isn't a valid input for any day", file.display());
+        return ExitCode::FAILURE;
+    }
+
+    for candidate in candidates {
+        println!(
+            "day {:>2} ({}): {:.0}%",
+            candidate.day.day,
+            candidate.day.title,
+            candidate.confidence * 100.0
+        );
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn inspect(day: u8, input: Option<&Path>, format: Format) -> ExitCode {
+    let day = days::get(day).expect("the day was checked when parsing it");
+    let input = match input.map(fs::read_to_string).transpose() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("couldn't read the input: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let stats = match (day.inspect)(input.as_deref().unwrap_or(day.input)) {
+        Ok(stats) => stats,
+        Err(err) => {
+            eprintln!("invalid input for day {}: {err}", day.day);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match format {
+        Format::Text => print!("{stats}"),
+        Format::Json => println!("{}", json!({ "day": day.day, "stats": stats })),
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_batch(inputs: &Path, timeout: Duration, cache: Option<&Cache>, format: Format) -> ExitCode {
+    let users = match batch::load_users(inputs) {
+        Ok(users) => users,
+        Err(err) => {
+            eprintln!("couldn't load the inputs in {}: {err}", inputs.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let outcomes = batch::run(&users, Some(timeout), cache);
+    match format {
+        Format::Text => print!("{}", Matrix(&outcomes)),
+        Format::Json => println!("{}", json!(outcomes)),
+    }
+
+    if outcomes.iter().all(|outcome| outcome.is_ok()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// The project's README, next to `aoc.toml` if there's one and in the working directory otherwise.
+fn readme_path() -> PathBuf {
+    let dir = env::current_dir().unwrap_or_default();
+    let root = config::discover(&dir)
+        .and_then(|config| config.parent().map(Path::to_path_buf))
+        .unwrap_or(dir);
+
+    root.join("README.md")
+}
+
+fn write_report(
+    html: bool,
+    redact: bool,
+    output: Option<&Path>,
+    readme: bool,
+    year: u16,
+    timeout: Duration,
+    cache: Option<&Cache>,
+) -> ExitCode {
+    // check the README before spending the time to run every day
+    let readme = if readme {
+        let path = readme_path();
+        match fs::read_to_string(&path) {
+            Ok(readme) => Some((readme, path)),
+            Err(err) => {
+                eprintln!("couldn't read {}: {err}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        None
+    };
+
+    let rows = report::run(Some(timeout), cache);
+    let report = if html {
+        report::html(&rows, year, redact)
+    } else {
+        report::markdown(&rows, year, redact)
+    };
+
+    let (path, contents) = if let Some((readme, path)) = readme {
+        let Some(updated) = report::update_readme(&readme, &report) else {
+            eprintln!(
+                "{} has no {} and {} markers",
+                path.display(),
+                report::README_START,
+                report::README_END
+            );
+            return ExitCode::FAILURE;
+        };
+        (path, updated)
+    } else if let Some(output) = output {
+        (output.to_path_buf(), report)
+    } else {
+        print!("{report}");
+        return ExitCode::SUCCESS;
+    };
+
+    match fs::write(&path, contents) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("couldn't write {}: {err}", path.display());
+            ExitCode::FAILURE
+        }
     }
 }
@@ -0,0 +1,157 @@
+//! Exact integer number theory, so that the solutions don't have to rely on floating point
+//! approximations. Everything works on `i128` and returns `None` instead of overflowing.
+
+/// The greatest common divisor, always non-negative.
+pub fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, always non-negative.
+pub fn lcm(a: i128, b: i128) -> Option<i128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b).map(i128::abs)
+}
+
+/// `(g, x, y)` such that `a * x + b * y == g`, where `g` is the greatest common divisor of `a` and
+/// `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` such that `a * x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime
+/// and `modulus` is positive.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    if modulus <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `a / b` if `b` divides `a` and the quotient fits.
+pub fn exact_div(a: i128, b: i128) -> Option<i128> {
+    if a.checked_rem(b)? != 0 {
+        return None;
+    }
+
+    a.checked_div(b)
+}
+
+/// The determinant `a * d - b * c` of the 2×2 matrix `[[a, b], [c, d]]`, which is also the cross
+/// product of the vectors `(a, c)` and `(b, d)`.
+pub fn det(a: i128, b: i128, c: i128, d: i128) -> Option<i128> {
+    a.checked_mul(d)?.checked_sub(b.checked_mul(c)?)
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the chinese remainder theorem.
+/// The moduli have to be positive, but not coprime. Returns the smallest non-negative solution and
+/// the modulus it repeats with, or `None` if the congruences contradict each other or a modulus
+/// isn't positive.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, modulus), &(residue, other_modulus)| {
+            if other_modulus <= 0 {
+                return None;
+            }
+
+            // x + k * modulus ≡ residue (mod other_modulus)
+            let (g, inverse, _) = extended_gcd(modulus, other_modulus);
+            let difference = exact_div(residue.checked_sub(x)?, g)?;
+
+            let step = other_modulus / g;
+            let k = difference
+                .rem_euclid(step)
+                .checked_mul(inverse.rem_euclid(step))?
+                .rem_euclid(step);
+
+            let combined = lcm(modulus, other_modulus)?;
+            let x = x.checked_add(k.checked_mul(modulus)?)?.rem_euclid(combined);
+            Some((x, combined))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{crt, det, exact_div, extended_gcd, gcd, lcm, mod_inverse};
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(101, 103), Some(10403));
+        assert_eq!(lcm(0, 3), Some(0));
+        assert_eq!(lcm(i128::MAX, i128::MAX - 1), None);
+    }
+
+    #[test]
+    fn extended() {
+        for (a, b) in [(240, 46), (-240, 46), (46, 240), (7, 0), (0, 7)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(101, 103), Some(51));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, -11), None);
+    }
+
+    #[test]
+    fn division() {
+        assert_eq!(exact_div(12, 4), Some(3));
+        assert_eq!(exact_div(-12, 4), Some(-3));
+        assert_eq!(exact_div(12, 5), None);
+        assert_eq!(exact_div(12, 0), None);
+        assert_eq!(exact_div(i128::MIN, -1), None);
+        assert_eq!(exact_div(i128::MIN, 1), Some(i128::MIN));
+        assert_eq!(det(1, 2, 3, 4), Some(-2));
+        assert_eq!(det(i128::MAX, 0, 0, 2), None);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1, 3), (8, 5)]), Some((8, 15)));
+        // the moduli don't have to be coprime
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(1, 3), (0, 0)]), None);
+        assert_eq!(
+            crt(&[(7623 % 101, 101), (7623 % 103, 103)]),
+            Some((7623, 10403))
+        );
+    }
+}
@@ -0,0 +1,503 @@
+//! Helpers for the parsing every day needs: blank-line separated sections, integers, `key=value`
+//! fields and grids. Every helper works on a [Span], which remembers where it is in the original
+//! input so that errors can point at the offending line and column.
+//!
+//! The days [normalize] their input before parsing it, so that the helpers only ever see `\n` line
+//! endings.
+
+use std::{borrow::Cow, error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    EmptyInput,
+    InvalidInteger(String),
+    InvalidDigit(char),
+    UnexpectedChar(char),
+    MissingSeparator(String),
+    MissingField(String),
+    Missing(String),
+    WrongSectionCount { expected: usize, found: usize },
+    WrongItemCount { expected: usize, found: usize },
+    RaggedGrid { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyInput => write!(f, "input is empty"),
+            Self::InvalidInteger(text) => write!(f, "invalid integer `{text}`"),
+            Self::InvalidDigit(c) => write!(f, "invalid digit {c:?}"),
+            Self::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+            Self::MissingSeparator(sep) => write!(f, "missing separator {sep:?}"),
+            Self::MissingField(key) => write!(f, "missing field `{key}`"),
+            Self::Missing(what) => write!(f, "missing {what}"),
+            Self::WrongSectionCount { expected, found } => {
+                write!(f, "expected {expected} sections, found {found}")
+            }
+            Self::WrongItemCount { expected, found } => {
+                write!(f, "expected {expected} items, found {found}")
+            }
+            Self::RaggedGrid { expected, found } => {
+                write!(f, "expected a row of width {expected}, found width {found}")
+            }
+        }
+    }
+}
+
+/// A parsing failure at a 1-based line and column of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// `input` without the quirks of the editor which saved it: a byte order mark, CRLF line endings,
+/// whitespace at the end of lines and tabs, which become spaces. Lines and columns stay where they
+/// were, so errors still point at the right place in the original.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    let is_normal = !input.contains(['\r', '\t'])
+        && input
+            .split('\n')
+            .all(|line| !line.ends_with(char::is_whitespace));
+    if is_normal {
+        return Cow::Borrowed(input);
+    }
+
+    let mut normalized = String::with_capacity(input.len());
+    for (idx, line) in input.split('\n').enumerate() {
+        if idx > 0 {
+            normalized.push('\n');
+        }
+        normalized.extend(
+            line.trim_end()
+                .chars()
+                .map(|c| if c == '\t' { ' ' } else { c }),
+        );
+    }
+
+    Cow::Owned(normalized)
+}
+
+/// `input` the way a Windows editor might save it, with a byte order mark and CRLF line endings.
+#[cfg(test)]
+pub fn crlf(input: &str) -> String {
+    format!("\u{feff}{}", input.replace('\n', "\r\n"))
+}
+
+/// A slice of the input along with its 1-based position in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+
+    /// The span of `sub`, which must be a slice of this span's text.
+    fn sub(&self, sub: &'a str) -> Self {
+        let offset = sub.as_ptr() as usize - self.text.as_ptr() as usize;
+        debug_assert!(offset + sub.len() <= self.text.len(), "not a sub-slice");
+
+        let before = &self.text[..offset];
+        match before.rfind('\n') {
+            Some(newline) => Self {
+                text: sub,
+                line: self.line + before.matches('\n').count(),
+                column: before[newline + 1..].chars().count() + 1,
+            },
+            None => Self {
+                text: sub,
+                line: self.line,
+                column: self.column + before.chars().count(),
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn trim(&self) -> Self {
+        self.sub(self.text.trim())
+    }
+
+    /// Fails with [ParseErrorKind::EmptyInput] if there's nothing but whitespace.
+    pub fn non_empty(&self) -> Result<Self, ParseError> {
+        let trimmed = self.trim();
+
+        if trimmed.is_empty() {
+            Err(self.error(ParseErrorKind::EmptyInput))
+        } else {
+            Ok(trimmed)
+        }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + use<'a> {
+        let span = *self;
+        self.text.lines().map(move |line| span.sub(line))
+    }
+
+    /// Blocks of lines separated by blank lines, ignoring leading and trailing whitespace.
+    pub fn sections(&self) -> impl Iterator<Item = Span<'a>> + use<'a> {
+        let span = *self;
+        let mut rest = self.text.trim();
+
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+
+            let (section, tail) = rest.split_once("\n\n").unwrap_or((rest, ""));
+            rest = tail.trim_start_matches('\n');
+
+            Some(span.sub(section))
+        })
+    }
+
+    /// Exactly `N` sections, see [Span::sections].
+    pub fn sections_exact<const N: usize>(&self) -> Result<[Span<'a>; N], ParseError> {
+        let sections: Vec<_> = self.sections().collect();
+        let found = sections.len();
+
+        sections
+            .try_into()
+            .map_err(|_| self.error(ParseErrorKind::WrongSectionCount { expected: N, found }))
+    }
+
+    pub fn split_once(&self, separator: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        let (before, after) = self
+            .text
+            .split_once(separator)
+            .ok_or_else(|| self.error(ParseErrorKind::MissingSeparator(separator.to_string())))?;
+
+        Ok((self.sub(before), self.sub(after)))
+    }
+
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Span<'a>> + use<'a> {
+        let span = *self;
+        self.text.split(separator).map(move |part| span.sub(part))
+    }
+
+    pub fn split_whitespace(&self) -> impl Iterator<Item = Span<'a>> + use<'a> {
+        let span = *self;
+        self.text.split_whitespace().map(move |part| span.sub(part))
+    }
+
+    /// Parses the whole (trimmed) span as an integer.
+    pub fn integer<T: FromStr>(&self) -> Result<T, ParseError> {
+        let trimmed = self.trim();
+
+        trimmed
+            .text
+            .parse()
+            .map_err(|_| trimmed.error(ParseErrorKind::InvalidInteger(trimmed.text.to_string())))
+    }
+
+    /// Every integer in the span, skipping any text in between. A `-` right before the digits
+    /// makes them negative, a `+` is ignored.
+    pub fn integers<T: FromStr>(&self) -> Result<Vec<T>, ParseError> {
+        let text = self.text;
+        let bytes = text.as_bytes();
+        let mut integers = vec![];
+        let mut idx = 0;
+
+        while idx < bytes.len() {
+            if !bytes[idx].is_ascii_digit() {
+                idx += 1;
+                continue;
+            }
+
+            let start = if idx > 0 && bytes[idx - 1] == b'-' {
+                idx - 1
+            } else {
+                idx
+            };
+
+            while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+                idx += 1;
+            }
+
+            integers.push(self.sub(&text[start..idx]).integer()?);
+        }
+
+        Ok(integers)
+    }
+
+    /// Exactly `N` integers, see [Span::integers].
+    pub fn integers_exact<T: FromStr, const N: usize>(&self) -> Result<[T; N], ParseError> {
+        let integers = self.integers()?;
+        let found = integers.len();
+
+        integers
+            .try_into()
+            .map_err(|_| self.error(ParseErrorKind::WrongItemCount { expected: N, found }))
+    }
+
+    /// Whitespace separated `key=value` pairs, such as `p=0,4 v=3,-3`.
+    pub fn fields(&self) -> Result<Vec<(Span<'a>, Span<'a>)>, ParseError> {
+        self.split_whitespace()
+            .map(|field| field.split_once("="))
+            .collect()
+    }
+
+    /// The value of the `key=value` pair with the given key, see [Span::fields].
+    pub fn field(&self, key: &str) -> Result<Span<'a>, ParseError> {
+        self.find_field(self.fields()?, key)
+    }
+
+    /// Pairs of a key and a value joined by `assign` and separated by `separator`, such as
+    /// `X+94, Y+34` with `", "` and `"+"`.
+    pub fn fields_by(
+        &self,
+        separator: &'a str,
+        assign: &str,
+    ) -> Result<Vec<(Span<'a>, Span<'a>)>, ParseError> {
+        self.split(separator)
+            .map(|field| field.trim().split_once(assign))
+            .collect()
+    }
+
+    /// The value of the pair with the given key, see [Span::fields_by].
+    pub fn field_by(
+        &self,
+        key: &str,
+        separator: &'a str,
+        assign: &str,
+    ) -> Result<Span<'a>, ParseError> {
+        self.find_field(self.fields_by(separator, assign)?, key)
+    }
+
+    fn find_field(
+        &self,
+        fields: Vec<(Span<'a>, Span<'a>)>,
+        key: &str,
+    ) -> Result<Span<'a>, ParseError> {
+        fields
+            .into_iter()
+            .find_map(|(k, value)| (k.text == key).then_some(value))
+            .ok_or_else(|| self.error(ParseErrorKind::MissingField(key.to_string())))
+    }
+
+    /// Every character of the span along with its own span.
+    pub fn chars(&self) -> impl Iterator<Item = (Span<'a>, char)> + use<'a> {
+        let span = *self;
+        self.text
+            .char_indices()
+            .map(move |(idx, c)| (span.sub(&span.text[idx..idx + c.len_utf8()]), c))
+    }
+
+    /// Every character of the span as a base 10 digit.
+    pub fn digits(&self) -> Result<Vec<u8>, ParseError> {
+        self.chars()
+            .map(|(span, c)| {
+                c.to_digit(10)
+                    .map(|digit| digit as u8)
+                    .ok_or_else(|| span.error(ParseErrorKind::InvalidDigit(c)))
+            })
+            .collect()
+    }
+
+    /// A rectangular grid of digits, indexed as `grid[y][x]`.
+    pub fn digit_grid(&self) -> Result<Vec<Vec<u8>>, ParseError> {
+        let grid = self
+            .non_empty()?
+            .lines()
+            .map(|line| line.digits())
+            .collect();
+        check_rectangular(self, grid)
+    }
+
+    /// A rectangular grid of characters, indexed as `grid[y][x]`.
+    pub fn char_grid(&self) -> Result<Vec<Vec<char>>, ParseError> {
+        let grid = self
+            .non_empty()?
+            .lines()
+            .map(|line| Ok(line.text.chars().collect()))
+            .collect();
+        check_rectangular(self, grid)
+    }
+}
+
+fn check_rectangular<T>(
+    span: &Span,
+    grid: Result<Vec<Vec<T>>, ParseError>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let grid = grid?;
+    let width = grid[0].len();
+    let trimmed = span.trim();
+
+    match grid.iter().position(|row| row.len() != width) {
+        Some(y) => Err(ParseError {
+            line: trimmed.line + y,
+            column: 1,
+            kind: ParseErrorKind::RaggedGrid {
+                expected: width,
+                found: grid[y].len(),
+            },
+        }),
+        None => Ok(grid),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use super::{crlf, normalize, ParseError, ParseErrorKind, Span};
+
+    #[test]
+    fn normalization() {
+        assert!(matches!(
+            normalize("a b\n\nc\n"),
+            Cow::Borrowed("a b\n\nc\n")
+        ));
+        assert_eq!(normalize(&crlf("a\n\nb\n")), "a\n\nb\n");
+        assert_eq!(normalize("a \t\n  \nb\r\n\t"), "a\n\nb\n");
+        assert_eq!(normalize("3\t4\n"), "3 4\n");
+
+        let error = Span::new(&normalize(&crlf("12\r\n3x\n")))
+            .digit_grid()
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn sections() {
+        let input = "\na\nb\n\n\nc\n\nd\n";
+        let sections: Vec<_> = Span::new(input).sections().collect();
+
+        assert_eq!(
+            sections
+                .iter()
+                .map(|s| (s.text, s.line, s.column))
+                .collect::<Vec<_>>(),
+            [("a\nb", 2, 1), ("c", 6, 1), ("d", 8, 1)]
+        );
+
+        assert_eq!(
+            Span::new(input).sections_exact::<2>(),
+            Err(ParseError {
+                line: 1,
+                column: 1,
+                kind: ParseErrorKind::WrongSectionCount {
+                    expected: 2,
+                    found: 3
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn integers() {
+        let span = Span::new("Button A: X+94, Y-34\nPrize: X=8400, Y=5400");
+
+        assert_eq!(span.integers::<i64>(), Ok(vec![94, -34, 8400, 5400]));
+        assert_eq!(span.integers_exact::<i64, 4>(), Ok([94, -34, 8400, 5400]));
+        assert_eq!(
+            span.integers_exact::<i64, 3>().unwrap_err().kind,
+            ParseErrorKind::WrongItemCount {
+                expected: 3,
+                found: 4
+            }
+        );
+
+        let error = span.lines().nth(1).unwrap().integers::<u8>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 10: invalid integer `8400`"
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let line = Span::new("x\np=0,4 v=3,-3").lines().nth(1).unwrap();
+
+        assert_eq!(line.field("p").unwrap().integers(), Ok(vec![0, 4]));
+        assert_eq!(line.field("v").unwrap().integers(), Ok(vec![3, -3]));
+        assert_eq!(
+            line.field("q").unwrap_err(),
+            ParseError {
+                line: 2,
+                column: 1,
+                kind: ParseErrorKind::MissingField("q".to_string())
+            }
+        );
+
+        let error = Span::new("p=1 v").fields().unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+
+        let coordinates = Span::new("Prize: X=8400, Y=5400")
+            .split_once(": ")
+            .unwrap()
+            .1;
+        assert_eq!(
+            coordinates.field_by("Y", ", ", "=").unwrap().integer(),
+            Ok(5400)
+        );
+        let error = coordinates.field_by("X", ", ", "+").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 8));
+    }
+
+    #[test]
+    fn grids() {
+        assert_eq!(
+            Span::new("\n012\n345\n").digit_grid(),
+            Ok(vec![vec![0, 1, 2], vec![3, 4, 5]])
+        );
+        assert_eq!(
+            Span::new("\n012\n3x5\n").digit_grid(),
+            Err(ParseError {
+                line: 3,
+                column: 2,
+                kind: ParseErrorKind::InvalidDigit('x')
+            })
+        );
+        assert_eq!(
+            Span::new("\n#.#\n.#\n").char_grid(),
+            Err(ParseError {
+                line: 3,
+                column: 1,
+                kind: ParseErrorKind::RaggedGrid {
+                    expected: 3,
+                    found: 2
+                }
+            })
+        );
+        assert_eq!(
+            Span::new(" \n ").char_grid().unwrap_err().kind,
+            ParseErrorKind::EmptyInput
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Progress reporting for long-running solutions.
+//!
+//! Solutions announce the phase they're in with [start] and report each item they're done with
+//! with [advance]. Both are no-ops unless the solution runs under [track], which records the
+//! progress in a [Tracker] that another thread can poll, e.g. to draw a [Bar].
+
+use std::{
+    cell::OnceCell,
+    io::{self, IsTerminal, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+thread_local! {
+    static TRACKER: OnceCell<Arc<Tracker>> = const { OnceCell::new() };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub phase: &'static str,
+    /// Never more than `total`, if it's known, even if a solution advances past it.
+    pub done: u64,
+    /// 0 if the number of items isn't known up front.
+    pub total: u64,
+}
+
+/// The progress of a solution, shared between the solution's thread and whoever displays it.
+#[derive(Debug, Default)]
+pub struct Tracker {
+    phase: Mutex<&'static str>,
+    done: AtomicU64,
+    total: AtomicU64,
+}
+
+impl Tracker {
+    pub fn progress(&self) -> Progress {
+        let done = self.done.load(Ordering::Relaxed);
+        let total = self.total.load(Ordering::Relaxed);
+
+        Progress {
+            phase: *self.phase.lock().unwrap(),
+            done: if total == 0 { done } else { done.min(total) },
+            total,
+        }
+    }
+}
+
+fn with_tracker(f: impl FnOnce(&Tracker)) {
+    TRACKER.with(|tracker| {
+        if let Some(tracker) = tracker.get() {
+            f(tracker);
+        }
+    });
+}
+
+/// Starts a new phase of `total` items (0 if unknown).
+pub fn start(phase: &'static str, total: u64) {
+    with_tracker(|tracker| {
+        *tracker.phase.lock().unwrap() = phase;
+        tracker.done.store(0, Ordering::Relaxed);
+        tracker.total.store(total, Ordering::Relaxed);
+    });
+}
+
+/// Marks one more item of the current phase as done.
+pub fn advance() {
+    with_tracker(|tracker| {
+        tracker.done.fetch_add(1, Ordering::Relaxed);
+    });
+}
+
+/// Runs `f`, recording the progress it reports on this thread into `tracker`. Only the first
+/// tracker of a thread is used, solutions are meant to run on their own thread.
+pub fn track<T>(tracker: Arc<Tracker>, f: impl FnOnce() -> T) -> T {
+    let _ = TRACKER.with(|cell| cell.set(tracker));
+    f()
+}
+
+/// A progress bar on stderr, redrawn until it's dropped.
+pub struct Bar {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Bar {
+    const WIDTH: u64 = 30;
+    const REFRESH: Duration = Duration::from_millis(100);
+
+    /// Draws the progress of `tracker`, if stderr is a terminal.
+    pub fn show(tracker: Arc<Tracker>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = io::stderr().is_terminal().then(|| {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(Self::REFRESH);
+
+                    let progress = tracker.progress();
+                    if !progress.phase.is_empty() {
+                        eprint!("\r\x1b[2K{}", Self::render(&progress));
+                        let _ = io::stderr().flush();
+                    }
+                }
+
+                eprint!("\r\x1b[2K");
+            })
+        });
+
+        Self { stop, thread }
+    }
+
+    fn render(progress: &Progress) -> String {
+        let Progress { phase, done, total } = *progress;
+        if total == 0 {
+            return format!("{phase}: {done}");
+        }
+
+        let done = done.min(total);
+        let filled = (done * Self::WIDTH / total) as usize;
+        let empty = Self::WIDTH as usize - filled;
+        format!(
+            "{phase} [{}{}] {}% ({done}/{total})",
+            "#".repeat(filled),
+            " ".repeat(empty),
+            done * 100 / total
+        )
+    }
+}
+
+impl Drop for Bar {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::{advance, start, track, Bar, Progress, Tracker};
+
+    #[test]
+    fn tracking() {
+        let tracker = Arc::new(Tracker::default());
+
+        let answer = track(tracker.clone(), || {
+            start("counting", 10);
+            for _ in 0..4 {
+                advance();
+            }
+            42
+        });
+
+        assert_eq!(answer, 42);
+        assert_eq!(
+            tracker.progress(),
+            Progress {
+                phase: "counting",
+                done: 4,
+                total: 10
+            }
+        );
+    }
+
+    #[test]
+    fn overshooting() {
+        let tracker = Arc::new(Tracker::default());
+        track(tracker.clone(), || {
+            start("counting", 3);
+            for _ in 0..5 {
+                advance();
+            }
+        });
+
+        let progress = tracker.progress();
+        assert_eq!((progress.done, progress.total), (3, 3));
+        assert_eq!(
+            Bar::render(&progress),
+            format!("counting [{}] 100% (3/3)", "#".repeat(30))
+        );
+    }
+
+    #[test]
+    fn untracked() {
+        start("counting", 10);
+        advance();
+    }
+
+    #[test]
+    fn render() {
+        let progress = Progress {
+            phase: "placing obstructions",
+            done: 50,
+            total: 200,
+        };
+        assert_eq!(
+            Bar::render(&progress),
+            "placing obstructions [#######                       ] 25% (50/200)"
+        );
+
+        let progress = Progress {
+            phase: "waiting",
+            done: 7,
+            total: 0,
+        };
+        assert_eq!(Bar::render(&progress), "waiting: 7");
+    }
+}
@@ -0,0 +1,537 @@
+//! The `aoc2024` Python extension module, built with maturin (see `pyproject.toml`):
+//!
+//! - `solve(day, part, input=None, **params)` solves a part, like the CLI's `run`.
+//! - `dayN.part_1(input=None, **params)` and `dayN.part_2(...)` do the same for one day.
+//! - `dayN.parse(input=None)` returns the day's parsed input as Python objects: [Report]s,
+//!   [Machine]s, [Robot]s and [Region]s for days 2, 13, 14 and 12, and lists, tuples and dicts
+//!   for the others. Day 3 has no parser, as its instructions are only found while solving.
+//!
+//! Inputs default to the bundled puzzle input. Invalid inputs and parameters raise a `ValueError`.
+
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyCFunction, PyDict},
+    IntoPyObjectExt,
+};
+
+use crate::{
+    answer::Answer,
+    cancel::Failure,
+    days::{self, Day, Part},
+    parse::{normalize, ParseError},
+};
+
+/// A report of day 2, the levels of a reactor.
+#[pyclass(frozen, get_all, module = "aoc2024")]
+pub struct Report {
+    levels: Vec<isize>,
+}
+
+#[pymethods]
+impl Report {
+    fn __repr__(&self) -> String {
+        format!("Report(levels={:?})", self.levels)
+    }
+
+    fn __len__(&self) -> usize {
+        self.levels.len()
+    }
+}
+
+/// A claw machine of day 13, with how far each button moves the claw and where the prize is, as
+/// `(x, y)`.
+#[pyclass(frozen, get_all, module = "aoc2024")]
+pub struct Machine {
+    button_a: (i128, i128),
+    button_b: (i128, i128),
+    prize: (i128, i128),
+}
+
+#[pymethods]
+impl Machine {
+    fn __repr__(&self) -> String {
+        format!(
+            "Machine(button_a={:?}, button_b={:?}, prize={:?})",
+            self.button_a, self.button_b, self.prize
+        )
+    }
+}
+
+/// A robot of day 14, with its starting position and its velocity in tiles per second, as
+/// `(x, y)`.
+#[pyclass(frozen, get_all, module = "aoc2024")]
+pub struct Robot {
+    position: (isize, isize),
+    velocity: (isize, isize),
+}
+
+#[pymethods]
+impl Robot {
+    fn __repr__(&self) -> String {
+        format!(
+            "Robot(position={:?}, velocity={:?})",
+            self.position, self.velocity
+        )
+    }
+}
+
+/// A region of day 12's garden, with its cells as `(x, y)` from left to right and top to bottom.
+#[pyclass(frozen, get_all, module = "aoc2024")]
+pub struct Region {
+    plant: char,
+    area: usize,
+    perimeter: usize,
+    sides: usize,
+    cells: Vec<(usize, usize)>,
+}
+
+#[pymethods]
+impl Region {
+    fn __repr__(&self) -> String {
+        format!(
+            "Region(plant={:?}, area={}, perimeter={}, sides={})",
+            self.plant, self.area, self.perimeter, self.sides
+        )
+    }
+}
+
+fn value_error(err: impl ToString) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// The input of `day`, or its bundled puzzle input.
+fn input(day: u8, input: Option<&str>) -> &str {
+    input.unwrap_or_else(|| days::get(day).expect("the day is enabled").input)
+}
+
+// unused if no day with a parser is enabled
+#[allow(dead_code)]
+fn parsed<T>(
+    day: u8,
+    input: Option<&str>,
+    parse: fn(&str) -> Result<T, ParseError>,
+) -> PyResult<T> {
+    parse(&normalize(self::input(day, input))).map_err(value_error)
+}
+
+fn answer(py: Python<'_>, answer: Answer) -> PyResult<Bound<'_, PyAny>> {
+    match answer {
+        Answer::Number(n) => n.into_bound_py_any(py),
+        Answer::Text(text) => text.into_bound_py_any(py),
+        Answer::Lines(lines) => lines.into_bound_py_any(py),
+    }
+}
+
+/// Solves `part` of `day`, with the keyword arguments in `params` as its parameters.
+fn run<'py>(
+    py: Python<'py>,
+    day: &Day,
+    part: &Part,
+    input: Option<String>,
+    params: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let mut values = vec![];
+    for (name, value) in params.into_iter().flatten() {
+        values.push((
+            name.extract::<String>()?,
+            value.extract::<i64>()?.to_string(),
+        ));
+    }
+
+    let params = part
+        .params(
+            values
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        )
+        .map_err(value_error)?;
+
+    if let Some(input) = &input {
+        (day.validate)(input).map_err(value_error)?;
+    }
+
+    // long solutions don't hold up other Python threads, and the inputs a solution can't solve,
+    // e.g. day 14's robots which never draw a picture, are invalid too
+    let run = py
+        .detach(|| part.run(input.as_deref(), &params, None, None))
+        .map_err(|failure| match failure {
+            Failure::Panicked(message) => value_error(message),
+            Failure::TimedOut(_) => unreachable!("solutions run without a timeout"),
+        })?;
+    answer(py, run.answer)
+}
+
+/// Solves `part` of `day` for `input`, or the bundled puzzle input if there's none, with the
+/// part's parameters as keyword arguments, e.g. `solve(14, 1, example, width=11, height=7)`.
+#[pyfunction]
+#[pyo3(signature = (day, part, input=None, **params))]
+fn solve<'py>(
+    py: Python<'py>,
+    day: u8,
+    part: u8,
+    input: Option<String>,
+    params: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let day = days::get(day).ok_or_else(|| value_error(format!("day {day} isn't solved")))?;
+    let part = day
+        .part(part)
+        .ok_or_else(|| value_error(format!("day {} has no part {part}", day.day)))?;
+
+    run(py, day, part, input, params)
+}
+
+/// `dayN.part_1` or `dayN.part_2`, which takes the input as its only positional argument.
+fn part_function<'py>(
+    py: Python<'py>,
+    day: &'static Day,
+    part: &'static Part,
+) -> PyResult<Bound<'py, PyCFunction>> {
+    let (name, doc) = match part.part {
+        1 => (
+            c"part_1",
+            c"part_1(input=None, **params)\n--\n\nSolves part 1 for `input`.",
+        ),
+        _ => (
+            c"part_2",
+            c"part_2(input=None, **params)\n--\n\nSolves part 2 for `input`.",
+        ),
+    };
+
+    PyCFunction::new_closure(py, Some(name), Some(doc), move |args, kwargs| {
+        let py = args.py();
+        let params = kwargs.map(|kwargs| kwargs.copy()).transpose()?;
+
+        let input = match (args.len(), &params) {
+            (0, Some(params)) => params.get_item("input")?,
+            (1, _) => Some(args.get_item(0)?),
+            (0, None) => None,
+            _ => {
+                return Err(PyTypeError::new_err(
+                    "the input is the only positional argument",
+                ))
+            }
+        };
+        if let Some(params) = &params {
+            if params.contains("input")? {
+                params.del_item("input")?;
+            }
+        }
+
+        let input = input.map(|input| input.extract()).transpose()?;
+        run(py, day, part, input, params.as_ref()).map(Bound::unbind)
+    })
+}
+
+#[cfg(feature = "day1")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day1(input: Option<&str>) -> PyResult<(Vec<isize>, Vec<isize>)> {
+    parsed(1, input, crate::day1::parse)
+}
+
+#[cfg(feature = "day2")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day2(input: Option<&str>) -> PyResult<Vec<Report>> {
+    let reports = parsed(2, input, crate::day2::parse)?;
+    Ok(reports
+        .into_iter()
+        .map(|levels| Report { levels })
+        .collect())
+}
+
+/// The rows of the grid.
+#[cfg(feature = "day4")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day4(input: Option<&str>) -> PyResult<Vec<String>> {
+    let grid = parsed(4, input, crate::day4::parse)?;
+    Ok(grid.into_iter().map(String::from_iter).collect())
+}
+
+/// The ordering rules as `(before, after)` pairs and the updates.
+#[cfg(feature = "day5")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day5<'py>(py: Python<'py>, input: Option<&str>) -> PyResult<Bound<'py, PyDict>> {
+    let crate::day5::Output {
+        before_map,
+        page_updates,
+    } = parsed(5, input, crate::day5::parse)?;
+
+    let mut rules: Vec<_> = before_map
+        .into_iter()
+        .flat_map(|(before, afters)| afters.into_iter().map(move |after| (before, after)))
+        .collect();
+    rules.sort();
+    // lists of `u8` would become `bytes`
+    let updates: Vec<Vec<u16>> = page_updates
+        .into_iter()
+        .map(|update| update.into_iter().map(u16::from).collect())
+        .collect();
+
+    let dict = PyDict::new(py);
+    dict.set_item("rules", rules)?;
+    dict.set_item("updates", updates)?;
+    Ok(dict)
+}
+
+/// The size of the lab, the obstacles and where the guard is and which way it faces.
+#[cfg(feature = "day6")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day6<'py>(py: Python<'py>, input: Option<&str>) -> PyResult<Bound<'py, PyDict>> {
+    use crate::day6::Direction;
+
+    let lab = parsed(6, input, crate::day6::parse)?;
+    let (position, direction) = lab.guard;
+    let direction = match direction {
+        Direction::Up => '^',
+        Direction::Down => 'v',
+        Direction::Left => '<',
+        Direction::Right => '>',
+    };
+
+    let dict = PyDict::new(py);
+    dict.set_item("width", lab.width)?;
+    dict.set_item("height", lab.height)?;
+    dict.set_item("obstacles", lab.obstacles.iter().collect::<Vec<_>>())?;
+    dict.set_item("guard", position)?;
+    dict.set_item("direction", direction)?;
+    Ok(dict)
+}
+
+/// The equations as `(result, operands)`.
+#[cfg(feature = "day7")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day7(input: Option<&str>) -> PyResult<Vec<(usize, Vec<usize>)>> {
+    let equations = parsed(7, input, crate::day7::parse)?;
+    Ok(equations
+        .into_iter()
+        .map(|equation| (equation.result, equation.operands))
+        .collect())
+}
+
+/// The size of the map and the positions of the antennas of every frequency.
+#[cfg(feature = "day8")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day8<'py>(py: Python<'py>, input: Option<&str>) -> PyResult<Bound<'py, PyDict>> {
+    let map = parsed(8, input, crate::day8::parse)?;
+    let antennas = PyDict::new(py);
+    for (frequency, positions) in map.antennas {
+        let positions: Vec<_> = positions.into_iter().map(|pos| (pos.0, pos.1)).collect();
+        antennas.set_item(frequency, positions)?;
+    }
+
+    let dict = PyDict::new(py);
+    dict.set_item("width", map.width)?;
+    dict.set_item("height", map.height)?;
+    dict.set_item("antennas", antennas)?;
+    Ok(dict)
+}
+
+/// The disk map as `(file_id, size)`, where the file id of free space is `None`.
+#[cfg(feature = "day9")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day9(input: Option<&str>) -> PyResult<Vec<(Option<usize>, u8)>> {
+    use crate::day9::DiskEntry;
+
+    let disk = parsed(9, input, crate::day9::parse)?;
+    Ok(disk
+        .into_iter()
+        .map(|entry| match entry {
+            DiskEntry::File(file) => (Some(file.id), file.size),
+            DiskEntry::FreeSpace(size) => (None, size),
+        })
+        .collect())
+}
+
+/// The heights, as rows.
+#[cfg(feature = "day10")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day10(input: Option<&str>) -> PyResult<Vec<Vec<u16>>> {
+    let map = parsed(10, input, crate::day10::parse)?;
+    // lists of `u8` would become `bytes`
+    Ok(map
+        .into_iter()
+        .map(|row| row.into_iter().map(u16::from).collect())
+        .collect())
+}
+
+#[cfg(feature = "day11")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day11(input: Option<&str>) -> PyResult<Vec<u64>> {
+    parsed(11, input, crate::day11::parse)
+}
+
+#[cfg(feature = "day12")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day12(input: Option<&str>) -> PyResult<Vec<Region>> {
+    use crate::{
+        day12::{get_region_area, get_region_perimeter, get_region_sides},
+        parse::Span,
+    };
+
+    let garden = normalize(self::input(12, input));
+    let crate::day12::Input { regions } = crate::day12::parse(&garden).map_err(value_error)?;
+    // the same grid the regions' positions are in
+    let rows = Span::new(&garden).char_grid().map_err(value_error)?;
+
+    Ok(regions
+        .into_iter()
+        .map(|region| {
+            let (origin_x, origin_y) = region.origin;
+            let mut cells: Vec<_> = region
+                .cells
+                .iter()
+                .map(|(x, y)| (origin_x + x, origin_y + y))
+                .collect();
+            cells.sort_by_key(|&(x, y)| (y, x));
+
+            let (x, y) = cells[0];
+            Region {
+                plant: rows[y][x],
+                area: get_region_area(&region.cells),
+                perimeter: get_region_perimeter(&region.cells),
+                sides: get_region_sides(&region.cells),
+                cells,
+            }
+        })
+        .collect())
+}
+
+#[cfg(feature = "day13")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day13(input: Option<&str>) -> PyResult<Vec<Machine>> {
+    let machines = parsed(13, input, crate::day13::parse)?;
+    Ok(machines
+        .into_iter()
+        .map(|machine| Machine {
+            button_a: (machine.button_a.x, machine.button_a.y),
+            button_b: (machine.button_b.x, machine.button_b.y),
+            prize: (machine.prize.x, machine.prize.y),
+        })
+        .collect())
+}
+
+#[cfg(feature = "day14")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day14(input: Option<&str>) -> PyResult<Vec<Robot>> {
+    let robots = parsed(14, input, crate::day14::parse)?;
+    Ok(robots
+        .into_iter()
+        .map(|robot| Robot {
+            position: (robot.pos.x, robot.pos.y),
+            velocity: (robot.vel.x, robot.vel.y),
+        })
+        .collect())
+}
+
+/// The size of the warehouse, its walls and boxes, where the robot starts and its moves.
+#[cfg(feature = "day15")]
+#[pyfunction]
+#[pyo3(name = "parse", signature = (input=None))]
+fn parse_day15<'py>(py: Python<'py>, input: Option<&str>) -> PyResult<Bound<'py, PyDict>> {
+    use crate::day15::{Direction, Item};
+
+    let crate::day15::Input { map, directions } = parsed(15, input, crate::day15::parse)?;
+
+    let positions = |wanted: Item| {
+        let mut positions: Vec<_> = map
+            .0
+            .iter()
+            .filter(|&(_, &item)| item == wanted)
+            .map(|(pos, _)| (pos.x, pos.y))
+            .collect();
+        positions.sort_by_key(|&(x, y)| (y, x));
+        positions
+    };
+    let moves: String = directions
+        .iter()
+        .map(|direction| match direction {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        })
+        .collect();
+
+    let dict = PyDict::new(py);
+    dict.set_item("width", map.1.x)?;
+    dict.set_item("height", map.1.y)?;
+    dict.set_item("walls", positions(Item::Wall))?;
+    dict.set_item("boxes", positions(Item::Block))?;
+    dict.set_item("robot", positions(Item::Robot)[0])?;
+    dict.set_item("moves", moves)?;
+    Ok(dict)
+}
+
+/// Adds `parse` to the module of `day`, if it has a parser.
+// `module` is unused if no day with a parser is enabled
+#[allow(unused_variables)]
+fn add_parser(module: &Bound<'_, PyModule>, day: u8) -> PyResult<()> {
+    match day {
+        #[cfg(feature = "day1")]
+        1 => module.add_function(wrap_pyfunction!(parse_day1, module)?),
+        #[cfg(feature = "day2")]
+        2 => module.add_function(wrap_pyfunction!(parse_day2, module)?),
+        #[cfg(feature = "day4")]
+        4 => module.add_function(wrap_pyfunction!(parse_day4, module)?),
+        #[cfg(feature = "day5")]
+        5 => module.add_function(wrap_pyfunction!(parse_day5, module)?),
+        #[cfg(feature = "day6")]
+        6 => module.add_function(wrap_pyfunction!(parse_day6, module)?),
+        #[cfg(feature = "day7")]
+        7 => module.add_function(wrap_pyfunction!(parse_day7, module)?),
+        #[cfg(feature = "day8")]
+        8 => module.add_function(wrap_pyfunction!(parse_day8, module)?),
+        #[cfg(feature = "day9")]
+        9 => module.add_function(wrap_pyfunction!(parse_day9, module)?),
+        #[cfg(feature = "day10")]
+        10 => module.add_function(wrap_pyfunction!(parse_day10, module)?),
+        #[cfg(feature = "day11")]
+        11 => module.add_function(wrap_pyfunction!(parse_day11, module)?),
+        #[cfg(feature = "day12")]
+        12 => module.add_function(wrap_pyfunction!(parse_day12, module)?),
+        #[cfg(feature = "day13")]
+        13 => module.add_function(wrap_pyfunction!(parse_day13, module)?),
+        #[cfg(feature = "day14")]
+        14 => module.add_function(wrap_pyfunction!(parse_day14, module)?),
+        #[cfg(feature = "day15")]
+        15 => module.add_function(wrap_pyfunction!(parse_day15, module)?),
+        _ => Ok(()),
+    }
+}
+
+#[pymodule]
+fn aoc2024(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+
+    module.add_class::<Report>()?;
+    module.add_class::<Machine>()?;
+    module.add_class::<Robot>()?;
+    module.add_class::<Region>()?;
+    module.add_function(wrap_pyfunction!(solve, module)?)?;
+
+    for day in days::DAYS {
+        let day_module = PyModule::new(py, &format!("day{}", day.day))?;
+        day_module.add("title", day.title)?;
+        for part in &day.parts {
+            day_module.add(format!("part_{}", part.part), part_function(py, day, part)?)?;
+        }
+        add_parser(&day_module, day.day)?;
+
+        module.add_submodule(&day_module)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,252 @@
+//! Recordings of [simulations](crate::simulation), to share them instead of watching them go by in
+//! a terminal: asciinema casts, which replay the frames as text, and animated GIFs, which draw each
+//! character of a frame as a square of its own colour.
+
+use std::{borrow::Cow, io, io::Write};
+
+use serde_json::json;
+
+use crate::{
+    image::{Image, Rgb, TILES},
+    simulation::Player,
+};
+
+/// The most frames [frames] records, so that a simulation which never ends still makes a
+/// recording of a sensible size.
+pub const MAX_FRAMES: usize = 10_000;
+
+/// The width and height of a character in a GIF, in pixels.
+const SCALE: usize = 4;
+
+/// The colour of a character of a frame, from [TILES].
+fn colour(c: char) -> Rgb {
+    TILES.category(match c {
+        '.' | ' ' => 0,
+        '#' => 1,
+        'O' | '[' | ']' => 2,
+        '@' | '^' | 'v' | '<' | '>' => 3,
+        '0'..='9' => 4,
+        _ => 5,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub step: usize,
+    pub text: String,
+}
+
+/// The frames of `player` every `every` steps from its current one, up to step `last` or until
+/// it's done, and at most [MAX_FRAMES]. The last step is always recorded.
+pub fn frames(player: &mut dyn Player, last: usize, every: usize) -> Vec<Frame> {
+    assert!(every > 0, "frames are at least a step apart");
+
+    let mut frames = vec![];
+    loop {
+        frames.push(Frame {
+            step: player.position(),
+            text: player.render(),
+        });
+
+        let next = (player.position() + every).min(last);
+        if frames.len() == MAX_FRAMES || player.position() == last || player.is_done() {
+            return frames;
+        }
+
+        player.seek(next);
+    }
+}
+
+/// The width and height of the largest frame, in characters.
+fn size(frames: &[Frame]) -> (usize, usize) {
+    frames.iter().fold((0, 0), |(width, height), frame| {
+        let lines = frame.text.lines();
+        let frame_width = lines.clone().map(|line| line.chars().count()).max();
+        (
+            width.max(frame_width.unwrap_or(0)),
+            height.max(lines.count()),
+        )
+    })
+}
+
+/// Writes `frames` as an [asciinema v2](https://docs.asciinema.org/manual/asciicast/v2/) cast,
+/// `fps` frames a second, each with `title` and its step above it.
+pub fn write_cast(mut out: impl Write, frames: &[Frame], fps: u32, title: &str) -> io::Result<()> {
+    let (width, height) = size(frames);
+    let width = width.max(title.len() + 20);
+
+    // one more line for the caption
+    let header = json!({ "version": 2, "width": width, "height": height + 1, "title": title });
+    writeln!(out, "{header}")?;
+
+    for (idx, frame) in frames.iter().enumerate() {
+        let time = idx as f64 / f64::from(fps);
+        // move to the top left corner and clear the screen, as terminals need \r\n
+        let screen = format!(
+            "\x1b[H\x1b[2J{title}, step {}\r\n{}",
+            frame.step,
+            frame.text.replace('\n', "\r\n")
+        );
+        writeln!(out, "{}", json!([time, "o", screen]))?;
+    }
+
+    out.flush()
+}
+
+/// `frame` as a `width` × `height` image of characters, each a square of [SCALE] pixels.
+fn image(frame: &Frame, width: usize, height: usize) -> Image {
+    let lines: Vec<Vec<char>> = frame
+        .text
+        .lines()
+        .map(|line| line.chars().collect())
+        .collect();
+    let image = Image::from_fn(width, height, |x, y| {
+        let c = lines.get(y).and_then(|line| line.get(x));
+        colour(c.copied().unwrap_or(' '))
+    });
+
+    image.scaled(SCALE)
+}
+
+/// Writes `frames` as a GIF, `fps` frames a second and looping forever.
+pub fn write_gif(out: impl Write, frames: &[Frame], fps: u32) -> io::Result<()> {
+    let (width, height) = size(frames);
+    let (Ok(gif_width), Ok(gif_height)) =
+        (u16::try_from(width * SCALE), u16::try_from(height * SCALE))
+    else {
+        return Err(io::Error::other("the frames are too large for a GIF"));
+    };
+
+    let palette = TILES.0.concat();
+    let mut encoder =
+        gif::Encoder::new(out, gif_width, gif_height, &palette).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    // GIFs count in hundredths of seconds
+    let delay = (100 / fps.max(1)).max(1) as u16;
+    for frame in frames {
+        let pixels = image(frame, width, height)
+            .pixels()
+            .iter()
+            .map(|&pixel| {
+                TILES
+                    .index(pixel)
+                    .expect("frames only use the tiles' colours") as u8
+            })
+            .collect();
+
+        let frame = gif::Frame {
+            width: gif_width,
+            height: gif_height,
+            delay,
+            buffer: Cow::Owned(pixels),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    // dropping the encoder would panic if the end of the file can't be written
+    encoder.into_inner()?.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::{self, Display};
+
+    use super::{frames, write_cast, write_gif, Frame};
+    use crate::simulation::{Replay, Simulation};
+
+    /// A box moving right along a corridor.
+    struct Corridor {
+        pos: usize,
+    }
+
+    impl Display for Corridor {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let floor: String = (0..4)
+                .map(|x| if x == self.pos { 'O' } else { '.' })
+                .collect();
+            writeln!(f, "######\n#{floor}#\n######")
+        }
+    }
+
+    impl Simulation for Corridor {
+        type Snapshot = usize;
+
+        fn step(&mut self) {
+            self.pos += 1;
+        }
+
+        fn is_done(&self) -> bool {
+            self.pos == 3
+        }
+
+        fn snapshot(&self) -> usize {
+            self.pos
+        }
+
+        fn restore(&mut self, pos: &usize) {
+            self.pos = *pos;
+        }
+    }
+
+    fn recording(last: usize, every: usize) -> Vec<Frame> {
+        frames(&mut Replay::new(Corridor { pos: 0 }), last, every)
+    }
+
+    #[test]
+    fn recording_frames() {
+        let steps = |frames: Vec<Frame>| frames.iter().map(|f| f.step).collect::<Vec<_>>();
+
+        assert_eq!(steps(recording(usize::MAX, 1)), [0, 1, 2, 3]);
+        assert_eq!(steps(recording(usize::MAX, 2)), [0, 2, 3]);
+        assert_eq!(steps(recording(1, 1)), [0, 1]);
+        assert_eq!(recording(0, 1)[0].text, "######\n#O...#\n######\n");
+    }
+
+    #[test]
+    fn casts() {
+        let mut cast = vec![];
+        write_cast(&mut cast, &recording(usize::MAX, 1), 2, "corridor").unwrap();
+        let cast = String::from_utf8(cast).unwrap();
+
+        let lines: Vec<serde_json::Value> = cast
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 28);
+        assert_eq!(lines[0]["height"], 4);
+        assert_eq!(lines[2][0], 0.5);
+        assert_eq!(lines[2][1], "o");
+        assert_eq!(
+            lines[2][2],
+            "\x1b[H\x1b[2Jcorridor, step 1\r\n######\r\n#.O..#\r\n######\r\n"
+        );
+    }
+
+    #[test]
+    fn gifs() {
+        let mut gif = vec![];
+        write_gif(&mut gif, &recording(usize::MAX, 1), 10).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = decoder.read_info(gif.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (24, 12));
+
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            // the box is one cell further right in every frame
+            let row = 5 * 24;
+            assert_eq!(frame.buffer[row + 4 * (count + 1)], 2);
+            assert_eq!(frame.buffer[row], 1);
+            count += 1;
+        }
+        assert_eq!(count, 4);
+    }
+}
@@ -0,0 +1,232 @@
+//! A table of every day's answers and runtimes on the bundled inputs, as Markdown or HTML.
+//!
+//! The Markdown table also lives in the README, between [README_START] and [README_END], and
+//! [update_readme] replaces it there.
+
+use std::{fmt::Write, time::Duration};
+
+use crate::{
+    cache::{self, Cache},
+    cancel::Failure,
+    days::{self, Day, Run},
+};
+
+/// The year of the puzzles these are the solutions to.
+pub const YEAR: u16 = 2024;
+
+pub const README_START: &str = "<!-- report start -->";
+pub const README_END: &str = "<!-- report end -->";
+
+pub struct Row {
+    pub day: &'static Day,
+    pub parts: Vec<Result<Run, Failure>>,
+}
+
+/// Runs both parts of every day on the bundled inputs, taking the answers already in `cache`.
+pub fn run(timeout: Option<Duration>, cache: Option<&Cache>) -> Vec<Row> {
+    days::DAYS
+        .iter()
+        .map(|day| Row {
+            day,
+            parts: day
+                .parts
+                .iter()
+                .map(|part| {
+                    let params = part.params([]).expect("the defaults are valid");
+                    cache::run(cache, day, part, None, &params, timeout, None)
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+    if seconds >= 1.0 {
+        format!("{seconds:.2} s")
+    } else if seconds >= 1e-3 {
+        format!("{:.2} ms", seconds * 1e3)
+    } else {
+        format!("{:.0} µs", seconds * 1e6)
+    }
+}
+
+fn puzzle_url(year: u16, day: &Day) -> String {
+    format!("https://adventofcode.com/{year}/day/{}", day.day)
+}
+
+fn source_path(day: &Day) -> String {
+    format!("src/day{}.rs", day.day)
+}
+
+/// The answer and runtime of a part, with `escape` applied to the answer, or with the answer
+/// replaced by `redacted`.
+fn cells(
+    result: &Result<Run, Failure>,
+    redacted: Option<&str>,
+    escape: fn(&str) -> String,
+) -> [String; 2] {
+    match (result, redacted) {
+        (Ok(run), Some(redacted)) => [redacted.to_string(), format_duration(run.duration)],
+        (Ok(run), None) => [
+            escape(&run.answer.to_string()),
+            format_duration(run.duration),
+        ],
+        (Err(failure), _) => [escape(&failure.to_string()), "-".to_string()],
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\n', "<br>")
+}
+
+pub fn markdown(rows: &[Row], year: u16, redact: bool) -> String {
+    let mut table = String::new();
+    table.push_str("| Day | Title | Part 1 | Time | Part 2 | Time |\n");
+    table.push_str("| --: | ----- | -----: | ---: | -----: | ---: |\n");
+
+    for Row { day, parts } in rows {
+        write!(
+            table,
+            "| [{}]({}) | [{}]({}) |",
+            day.day,
+            source_path(day),
+            day.title,
+            puzzle_url(year, day)
+        )
+        .unwrap();
+
+        for part in parts {
+            let [answer, time] = cells(part, redact.then_some("*redacted*"), escape_markdown);
+            write!(table, " {answer} | {time} |").unwrap();
+        }
+        table.push('\n');
+    }
+
+    table
+}
+
+pub fn html(rows: &[Row], year: u16, redact: bool) -> String {
+    let mut page = String::new();
+    write!(
+        page,
+        concat!(
+            "<!DOCTYPE html>\n",
+            "<html>\n",
+            "<head>\n",
+            "<meta charset=\"utf-8\">\n",
+            "<title>Advent of code {}</title>\n",
+            "</head>\n",
+            "<body>\n",
+            "<table>\n",
+            "<tr><th>Day</th><th>Title</th><th>Part 1</th><th>Time</th><th>Part 2</th><th>Time</th></tr>\n",
+        ),
+        year
+    )
+    .unwrap();
+
+    for Row { day, parts } in rows {
+        write!(
+            page,
+            "<tr><td><a href=\"{}\">{}</a></td><td><a href=\"{}\">{}</a></td>",
+            source_path(day),
+            day.day,
+            puzzle_url(year, day),
+            escape_html(day.title)
+        )
+        .unwrap();
+
+        for part in parts {
+            let [answer, time] = cells(part, redact.then_some("<i>redacted</i>"), escape_html);
+            write!(page, "<td>{answer}</td><td>{time}</td>").unwrap();
+        }
+        page.push_str("</tr>\n");
+    }
+
+    page.push_str("</table>\n</body>\n</html>\n");
+    page
+}
+
+/// Replaces the table between the README's markers, or returns `None` if it has no markers.
+pub fn update_readme(readme: &str, table: &str) -> Option<String> {
+    let start = readme.find(README_START)? + README_START.len();
+    let end = start + readme[start..].find(README_END)?;
+
+    Some(format!("{}\n{table}{}", &readme[..start], &readme[end..]))
+}
+
+#[cfg(all(test, feature = "day1"))]
+mod test {
+    use std::time::Duration;
+
+    use super::{format_duration, html, markdown, update_readme, Row, YEAR};
+    use crate::{cancel::Failure, days::Run};
+
+    fn rows() -> Vec<Row> {
+        let day = crate::days::get(1).unwrap();
+        vec![Row {
+            day,
+            parts: vec![
+                Ok(Run {
+                    answer: "a|b".into(),
+                    duration: Duration::from_micros(1500),
+                }),
+                Err(Failure::TimedOut(Duration::from_secs(5))),
+            ],
+        }]
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_micros(42)), "42 µs");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50 ms");
+        assert_eq!(format_duration(Duration::from_millis(13_700)), "13.70 s");
+    }
+
+    #[test]
+    fn tables() {
+        let table = markdown(&rows(), YEAR, false);
+        assert_eq!(
+            table.lines().nth(2).unwrap(),
+            "| [1](src/day1.rs) | [Historian Hysteria](https://adventofcode.com/2024/day/1) | \
+             a\\|b | 1.50 ms | timed out after 5s | - |"
+        );
+        assert!(markdown(&rows(), YEAR, true).contains("| *redacted* | 1.50 ms |"));
+
+        let page = html(&rows(), YEAR, false);
+        assert!(page.contains("<td><a href=\"src/day1.rs\">1</a></td>"));
+        assert!(page.contains("<td>a|b</td><td>1.50 ms</td>"));
+        assert!(html(&rows(), YEAR, true).contains("<td><i>redacted</i></td><td>1.50 ms</td>"));
+    }
+
+    #[test]
+    fn readme() {
+        let readme = "# Title\n<!-- report start -->\nold\n<!-- report end -->\nrest\n";
+        assert_eq!(
+            update_readme(readme, "new\n").unwrap(),
+            "# Title\n<!-- report start -->\nnew\n<!-- report end -->\nrest\n"
+        );
+        assert_eq!(update_readme("# Title\n", "new\n"), None);
+    }
+
+    /// The runtimes in the README go stale, but it should at least list every day.
+    #[test]
+    #[cfg(feature = "all")]
+    fn readme_lists_every_day() {
+        let readme = include_str!("../README.md");
+        for day in crate::days::DAYS {
+            let link = format!("| [{}](src/day{}.rs) | [{}]", day.day, day.day, day.title);
+            assert!(
+                readme.contains(&link),
+                "README.md is missing {link}, run `report --readme`"
+            );
+        }
+    }
+}
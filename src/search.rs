@@ -0,0 +1,485 @@
+//! Graph searches generic over a neighbours function, so the same code can walk grids, state
+//! spaces or anything else that can list the nodes reachable from a given node.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A cost of an edge or path. Blanket implemented for the unsigned and signed integer types.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// Walks back from `goal` through `parents` until reaching a node without a parent (a start node).
+pub fn reconstruct_path<N: Eq + Hash + Clone>(parents: &HashMap<N, N>, goal: &N) -> Vec<N> {
+    let mut path = vec![goal.clone()];
+
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+/// Every node reachable from `starts`, including the starts themselves.
+pub fn reachable<N, FN, IN>(starts: impl IntoIterator<Item = N>, mut neighbours: FN) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut visited: HashSet<N> = starts.into_iter().collect();
+    let mut stack: Vec<N> = visited.iter().cloned().collect();
+
+    while let Some(node) = stack.pop() {
+        for next in neighbours(&node) {
+            if visited.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+
+    visited
+}
+
+/// Shortest path (in number of edges) from any of `starts` to the first node matching `is_goal`.
+pub fn bfs<N, FN, IN, FG>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: FN,
+    mut is_goal: FG,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    let mut parents = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if visited.insert(start.clone()) {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(reconstruct_path(&parents, &node));
+        }
+
+        for next in neighbours(&node) {
+            if visited.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Cheapest path from any of `starts` to the first node matching `is_goal`, along with its cost.
+pub fn dijkstra<N, C, FN, IN, FG>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: FN,
+    is_goal: FG,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like [dijkstra], but guided by `heuristic`, which must never overestimate the remaining cost to
+/// a goal for the result to be the cheapest path.
+pub fn astar<N, C, FN, IN, FH, FG>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    let mut parents = HashMap::new();
+    let mut costs = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        costs.insert(start.clone(), C::default());
+        queue.push(Reverse((heuristic(&start), C::default(), start)));
+    }
+
+    while let Some(Reverse((_, cost, node))) = queue.pop() {
+        if costs.get(&node).is_some_and(|&best| best < cost) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some((reconstruct_path(&parents, &node), cost));
+        }
+
+        for (next, edge_cost) in neighbours(&node) {
+            let next_cost = cost + edge_cost;
+
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                queue.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// The cheapest cost to every node reachable from the starts, along with every predecessor that
+/// lies on some cheapest path to it.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N, C> {
+    pub costs: HashMap<N, C>,
+    pub predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Eq + Hash + Clone, C: Cost> ShortestPaths<N, C> {
+    /// Runs dijkstra over the whole graph reachable from `starts`, keeping all predecessors.
+    pub fn new<FN, IN>(starts: impl IntoIterator<Item = N>, mut neighbours: FN) -> Self
+    where
+        N: Ord,
+        FN: FnMut(&N) -> IN,
+        IN: IntoIterator<Item = (N, C)>,
+    {
+        let mut costs = HashMap::new();
+        let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+
+        for start in starts {
+            costs.insert(start.clone(), C::default());
+            queue.push(Reverse((C::default(), start)));
+        }
+
+        while let Some(Reverse((cost, node))) = queue.pop() {
+            if costs.get(&node).is_some_and(|&best| best < cost) {
+                continue;
+            }
+
+            for (next, edge_cost) in neighbours(&node) {
+                let next_cost = cost + edge_cost;
+
+                match costs.get(&next) {
+                    Some(&best) if next_cost > best => {}
+                    Some(&best) if next_cost == best => {
+                        predecessors.entry(next).or_default().push(node.clone());
+                    }
+                    _ => {
+                        costs.insert(next.clone(), next_cost);
+                        predecessors.insert(next.clone(), vec![node.clone()]);
+                        queue.push(Reverse((next_cost, next)));
+                    }
+                }
+            }
+        }
+
+        Self {
+            costs,
+            predecessors,
+        }
+    }
+
+    /// Same as [ShortestPaths::new] for unweighted graphs, where every edge costs 1.
+    pub fn unweighted<FN, IN>(starts: impl IntoIterator<Item = N>, mut neighbours: FN) -> Self
+    where
+        N: Ord,
+        C: From<u8>,
+        FN: FnMut(&N) -> IN,
+        IN: IntoIterator<Item = N>,
+    {
+        Self::new(starts, |node| {
+            neighbours(node).into_iter().map(|next| (next, C::from(1)))
+        })
+    }
+
+    pub fn cost_to(&self, goal: &N) -> Option<C> {
+        self.costs.get(goal).copied()
+    }
+
+    /// One of the cheapest paths from a start to `goal`.
+    pub fn path_to(&self, goal: &N) -> Option<Vec<N>> {
+        self.costs.get(goal)?;
+
+        let parents = reconstruct_path_parents(&self.predecessors, goal);
+        Some(reconstruct_path(&parents, goal))
+    }
+
+    /// Every node that lies on at least one cheapest path from a start to `goal`.
+    pub fn nodes_on_paths_to(&self, goal: &N) -> HashSet<N> {
+        if !self.costs.contains_key(goal) {
+            return HashSet::new();
+        }
+
+        reachable([goal.clone()], |node| {
+            self.predecessors.get(node).cloned().unwrap_or_default()
+        })
+    }
+
+    /// How many distinct cheapest paths lead from the starts to `goal`.
+    pub fn count_paths_to(&self, goal: &N) -> usize {
+        fn count<N: Eq + Hash + Clone>(
+            predecessors: &HashMap<N, Vec<N>>,
+            node: &N,
+            memo: &mut HashMap<N, usize>,
+        ) -> usize {
+            if let Some(&count) = memo.get(node) {
+                return count;
+            }
+
+            let total = match predecessors.get(node) {
+                Some(parents) => parents.iter().map(|p| count(predecessors, p, memo)).sum(),
+                None => 1,
+            };
+
+            memo.insert(node.clone(), total);
+            total
+        }
+
+        if !self.costs.contains_key(goal) {
+            return 0;
+        }
+
+        count(&self.predecessors, goal, &mut HashMap::new())
+    }
+}
+
+/// Picks the first predecessor of every node on the way back from `goal`.
+fn reconstruct_path_parents<N: Eq + Hash + Clone>(
+    predecessors: &HashMap<N, Vec<N>>,
+    goal: &N,
+) -> HashMap<N, N> {
+    let mut parents = HashMap::new();
+    let mut node = goal.clone();
+
+    while let Some(parent) = predecessors.get(&node).and_then(|p| p.first()) {
+        parents.insert(node, parent.clone());
+        node = parent.clone();
+    }
+
+    parents
+}
+
+/// Adapters to search over character or number grids indexed as `grid[y][x]`.
+pub mod grid {
+    /// An `(x, y)` position in a grid.
+    pub type Pos = (usize, usize);
+
+    /// Up, down, left and right deltas.
+    pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+    /// The orthogonal neighbours of `pos` that are inside a `width` x `height` grid.
+    pub fn neighbours(
+        (x, y): Pos,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = Pos> + Clone {
+        ORTHOGONAL.into_iter().filter_map(move |(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+
+            (x < width && y < height).then_some((x, y))
+        })
+    }
+
+    /// A grid that can be handed to the searches through its neighbours functions.
+    #[derive(Debug)]
+    pub struct Grid<'a, T> {
+        cells: &'a [Vec<T>],
+    }
+
+    // implemented by hand, as deriving would require `T: Copy`
+    impl<T> Clone for Grid<'_, T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<T> Copy for Grid<'_, T> {}
+
+    impl<'a, T> Grid<'a, T> {
+        pub fn new(cells: &'a [Vec<T>]) -> Self {
+            Self { cells }
+        }
+
+        pub fn width(&self) -> usize {
+            self.cells.first().map_or(0, Vec::len)
+        }
+
+        pub fn height(&self) -> usize {
+            self.cells.len()
+        }
+
+        pub fn get(&self, (x, y): Pos) -> Option<&'a T> {
+            self.cells.get(y)?.get(x)
+        }
+
+        /// Every position of the grid, row by row.
+        pub fn positions(&self) -> impl Iterator<Item = Pos> + use<'a, T> {
+            let cells = self.cells;
+            (0..cells.len()).flat_map(move |y| (0..cells[y].len()).map(move |x| (x, y)))
+        }
+
+        /// The orthogonal neighbours of `pos` for which `can_move(from, to)` holds.
+        pub fn neighbours_where<F>(&self, pos: Pos, can_move: F) -> impl Iterator<Item = Pos> + 'a
+        where
+            F: Fn(&T, &T) -> bool + 'a,
+        {
+            let grid = *self;
+            let from = self.get(pos);
+
+            neighbours(pos, self.width(), self.height()).filter(move |&next| {
+                match (from, grid.get(next)) {
+                    (Some(from), Some(to)) => can_move(from, to),
+                    _ => false,
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{grid::Grid, *};
+
+    static MAZE: &str = r"
+#######
+#S..#.#
+#.#.#.#
+#.#...#
+#...#E#
+#######
+";
+
+    fn parse_maze() -> Vec<Vec<char>> {
+        MAZE.trim().lines().map(|l| l.chars().collect()).collect()
+    }
+
+    fn find(maze: &[Vec<char>], c: char) -> grid::Pos {
+        Grid::new(maze)
+            .positions()
+            .find(|&pos| Grid::new(maze).get(pos) == Some(&c))
+            .unwrap()
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let maze = parse_maze();
+        let grid = Grid::new(&maze);
+        let (start, end) = (find(&maze, 'S'), find(&maze, 'E'));
+
+        let path = bfs(
+            [start],
+            |&pos| grid.neighbours_where(pos, |_, &to| to != '#'),
+            |&pos| pos == end,
+        )
+        .unwrap();
+
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert_eq!(path.len() - 1, 7);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_agree() {
+        let maze = parse_maze();
+        let grid = Grid::new(&maze);
+        let (start, end) = (find(&maze, 'S'), find(&maze, 'E'));
+        let neighbours = |&pos: &grid::Pos| {
+            grid.neighbours_where(pos, |_, &to| to != '#')
+                .map(|next| (next, 1u32))
+        };
+
+        let (_, dijkstra_cost) = dijkstra([start], neighbours, |&pos| pos == end).unwrap();
+        let (path, astar_cost) = astar(
+            [start],
+            neighbours,
+            |&(x, y)| (x.abs_diff(end.0) + y.abs_diff(end.1)) as u32,
+            |&pos| pos == end,
+        )
+        .unwrap();
+
+        assert_eq!(dijkstra_cost, 7);
+        assert_eq!(astar_cost, 7);
+        assert_eq!(path.len(), 8);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let path = bfs([0u8], |&n| (n < 5).then_some(n + 1), |&n| n == 10);
+        assert_eq!(path, None);
+
+        let result = dijkstra([0u8], |&n| (n < 5).then_some((n + 1, 1u8)), |&n| n == 10);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn weighted_dijkstra_prefers_cheaper_detour() {
+        // 0 -> 3 directly costs 10, going through 1 and 2 costs 3
+        let edges = |&n: &u8| match n {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+
+        assert_eq!(
+            dijkstra([0u8], edges, |&n| n == 3),
+            Some((vec![0, 1, 2, 3], 3u32))
+        );
+    }
+
+    #[test]
+    fn all_shortest_paths() {
+        // a diamond: 0 -> {1, 2} -> 3, plus a longer 0 -> 4 -> 5 -> 3 branch
+        let edges = |&n: &u8| match n {
+            0 => vec![1, 2, 4],
+            1 | 2 => vec![3],
+            4 => vec![5],
+            5 => vec![3],
+            _ => vec![],
+        };
+
+        let paths = ShortestPaths::<u8, u32>::unweighted([0], edges);
+
+        assert_eq!(paths.cost_to(&3), Some(2));
+        assert_eq!(paths.count_paths_to(&3), 2);
+        assert_eq!(paths.nodes_on_paths_to(&3), HashSet::from([0, 1, 2, 3]));
+        assert_eq!(paths.path_to(&3).map(|p| p.len()), Some(3));
+        assert_eq!(paths.count_paths_to(&42), 0);
+    }
+
+    #[test]
+    fn reachable_region() {
+        let maze = parse_maze();
+        let grid = Grid::new(&maze);
+
+        let walls = reachable([(0, 0)], |&pos| {
+            grid.neighbours_where(pos, |&from, &to| from == to)
+        });
+
+        // the outer wall, plus the two inner walls touching it
+        assert_eq!(walls.len(), 7 + 7 + 4 + 4 + 2 + 1);
+    }
+}
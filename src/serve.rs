@@ -0,0 +1,241 @@
+//! A local HTTP service exposing the solvers:
+//!
+//! - `GET /days` lists the days, their titles and the parameters of their parts.
+//! - `POST /solve/{day}/{part}?param=value` solves a part for the input in the body (or the
+//!   bundled puzzle input if the body is empty) and returns the answer and how long it took.
+//!
+//! Errors are returned as `{"error": "..."}` with a 4xx or 5xx status, a solution that runs for
+//! longer than the service's timeout is stopped with a 504.
+
+use std::{
+    io,
+    net::{SocketAddr, ToSocketAddrs},
+    thread,
+    time::Duration,
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{answer::Answer, cancel::Failure, days};
+
+#[derive(Serialize)]
+struct Solution {
+    day: u8,
+    part: u8,
+    answer: Answer,
+    duration_ms: f64,
+}
+
+pub struct Service {
+    server: Server,
+    timeout: Option<Duration>,
+}
+
+impl Service {
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let server = Server::http(addr).map_err(io::Error::other)?;
+        Ok(Self {
+            server,
+            timeout: None,
+        })
+    }
+
+    /// Stops solutions which run for longer than `timeout`.
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    /// Handles requests until the process is stopped, each on its own thread so that a slow
+    /// solution doesn't hold up the others.
+    pub fn run(self) {
+        for request in self.server.incoming_requests() {
+            let timeout = self.timeout;
+            thread::spawn(move || handle(request, timeout));
+        }
+    }
+}
+
+fn handle(mut request: Request, timeout: Option<Duration>) {
+    let mut body = String::new();
+    let (status, value) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => respond(request.method(), request.url(), &body, timeout),
+        Err(err) => error(400, format!("couldn't read the body: {err}")),
+    };
+
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+
+    if let Err(err) = request.respond(response) {
+        eprintln!("couldn't send the response: {err}");
+    }
+}
+
+fn error(status: u16, message: impl Into<String>) -> (u16, Value) {
+    (status, json!({ "error": message.into() }))
+}
+
+fn respond(method: &Method, url: &str, body: &str, timeout: Option<Duration>) -> (u16, Value) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (method, segments.as_slice()) {
+        (Method::Get, ["days"]) => (200, json!(days::DAYS)),
+        (Method::Post, ["solve", day, part]) => solve(day, part, query, body, timeout),
+        (_, ["days"] | ["solve", _, _]) => error(405, format!("{method} isn't allowed on {path}")),
+        _ => error(404, format!("no such endpoint: {path}")),
+    }
+}
+
+fn solve(
+    day: &str,
+    part: &str,
+    query: &str,
+    body: &str,
+    timeout: Option<Duration>,
+) -> (u16, Value) {
+    let Some(day) = day.parse().ok().and_then(days::get) else {
+        return error(404, format!("no such day: {day}"));
+    };
+    let Some(part) = part.parse().ok().and_then(|part| day.part(part)) else {
+        return error(404, format!("day {} has no part {part}", day.day));
+    };
+
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")));
+    let params = match part.params(query) {
+        Ok(params) => params,
+        Err(err) => return error(400, err.to_string()),
+    };
+
+    let input = (!body.trim().is_empty()).then_some(body);
+    if let Some(Err(err)) = input.map(day.validate) {
+        return error(400, format!("invalid input for day {}: {err}", day.day));
+    }
+
+    let run = match part.run(input, &params, timeout, None) {
+        Ok(run) => run,
+        Err(err @ Failure::TimedOut(_)) => return error(504, format!("the solution {err}")),
+        Err(err @ Failure::Panicked(_)) => return error(500, format!("the solution {err}")),
+    };
+
+    let solution = Solution {
+        day: day.day,
+        part: part.part,
+        answer: run.answer,
+        duration_ms: run.duration.as_secs_f64() * 1000.0,
+    };
+
+    (200, json!(solution))
+}
+
+// the tests use the examples of several days
+#[cfg(all(test, feature = "all"))]
+mod test {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+        thread,
+        time::Duration,
+    };
+
+    use serde_json::json;
+    use tiny_http::Method;
+
+    use super::{respond, Service};
+
+    #[test]
+    fn days() {
+        let (status, days) = respond(&Method::Get, "/days", "", None);
+        assert_eq!(status, 200);
+        assert_eq!(days[0]["title"], "Historian Hysteria");
+        assert_eq!(days[10]["parts"][1]["params"][0]["name"], "blinks");
+        assert_eq!(days[10]["parts"][1]["params"][0]["default"], 75);
+    }
+
+    #[test]
+    fn solve() {
+        let (status, solution) = respond(
+            &Method::Post,
+            "/solve/1/1",
+            "3 4\n4 3\n2 5\n1 3\n3 9\n3 3",
+            None,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(solution["answer"], 11);
+        assert!(solution["duration_ms"].is_f64());
+
+        let (status, solution) = respond(&Method::Post, "/solve/11/1?blinks=6", "125 17", None);
+        assert_eq!(status, 200);
+        assert_eq!(solution["answer"], 22);
+    }
+
+    #[test]
+    fn errors() {
+        let cases = [
+            (Method::Post, "/solve/26/1", "", 404),
+            (Method::Post, "/solve/1/3", "", 404),
+            (Method::Get, "/solve/1/1", "", 405),
+            (Method::Get, "/nope", "", 404),
+            (Method::Post, "/solve/1/1?steps=3", "", 400),
+            (Method::Post, "/solve/11/1?blinks=many", "1", 400),
+            (Method::Post, "/solve/1/1", "1 2 3", 400),
+        ];
+
+        for (method, url, body, expected) in cases {
+            let (status, response) = respond(&method, url, body, None);
+            assert_eq!(status, expected, "{method} {url}: {response}");
+            assert!(response["error"].is_string());
+        }
+    }
+
+    #[test]
+    fn timeout() {
+        // tries all 90000 cells as an obstruction, walking the guard across the lab for each
+        let mut lab = vec![".".repeat(300); 300];
+        lab[299].replace_range(150..151, "^");
+        let (status, response) = respond(
+            &Method::Post,
+            "/solve/6/2",
+            &lab.join("\n"),
+            Some(Duration::from_millis(50)),
+        );
+
+        assert_eq!(status, 504);
+        assert_eq!(response["error"], "the solution timed out after 50ms");
+    }
+
+    #[test]
+    fn over_http() {
+        let service = Service::bind("127.0.0.1:0").unwrap();
+        let addr = service.local_addr().unwrap();
+        thread::spawn(|| service.run());
+
+        let body = "125 17";
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /solve/11/1?blinks=25 HTTP/1.1\r\nHost: {addr}\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+
+        assert!(head.starts_with("HTTP/1.1 200"), "{head}");
+        let solution: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(solution["answer"], json!(55312));
+    }
+}
@@ -0,0 +1,199 @@
+//! Step by step simulations, like day 6's guard walking through the lab or day 15's robot pushing
+//! boxes around.
+//!
+//! A day implements [Simulation] for its state, and [Replay] drives it: it counts the steps and
+//! keeps a snapshot every so often, so that it can go back to any step it has been to by restoring
+//! the closest snapshot before it and replaying from there. Since [Replay] is a [Player], code
+//! which picks the simulation at runtime (e.g. through [Part::simulation](crate::days::Part)) can
+//! drive any of them the same way.
+
+use std::fmt::Display;
+
+pub trait Simulation: Display {
+    /// Whatever is needed to bring the simulation back to the state it was in.
+    type Snapshot;
+
+    /// Advances the simulation by one step, only called while it isn't done.
+    fn step(&mut self);
+
+    fn is_done(&self) -> bool;
+
+    fn snapshot(&self) -> Self::Snapshot;
+
+    fn restore(&mut self, snapshot: &Self::Snapshot);
+}
+
+/// Plays a simulation forwards and backwards, see [Replay].
+pub trait Player {
+    /// Advances by one step, returning whether there was one left.
+    fn step(&mut self) -> bool;
+
+    /// Goes to step `step`, or to the last step if the simulation is done before it, returning the
+    /// step it got to.
+    fn seek(&mut self, step: usize) -> usize;
+
+    /// The number of steps taken since the start.
+    fn position(&self) -> usize;
+
+    fn is_done(&self) -> bool;
+
+    fn render(&self) -> String;
+}
+
+pub struct Replay<S: Simulation> {
+    simulation: S,
+    position: usize,
+    /// The steps between snapshots.
+    interval: usize,
+    /// The snapshots at steps 0, `interval`, 2 × `interval`, ... up to the furthest step so far.
+    snapshots: Vec<S::Snapshot>,
+}
+
+impl<S: Simulation> Replay<S> {
+    const INTERVAL: usize = 64;
+
+    pub fn new(simulation: S) -> Self {
+        Self::with_interval(simulation, Self::INTERVAL)
+    }
+
+    pub fn with_interval(simulation: S, interval: usize) -> Self {
+        assert!(interval > 0, "the interval has to be at least 1");
+
+        Self {
+            snapshots: vec![simulation.snapshot()],
+            simulation,
+            position: 0,
+            interval,
+        }
+    }
+
+    pub fn simulation(&self) -> &S {
+        &self.simulation
+    }
+}
+
+impl<S: Simulation> Player for Replay<S> {
+    fn step(&mut self) -> bool {
+        if self.simulation.is_done() {
+            return false;
+        }
+
+        self.simulation.step();
+        self.position += 1;
+
+        let is_new = self.position / self.interval == self.snapshots.len();
+        if self.position.is_multiple_of(self.interval) && is_new {
+            self.snapshots.push(self.simulation.snapshot());
+        }
+
+        true
+    }
+
+    fn seek(&mut self, step: usize) -> usize {
+        if step < self.position {
+            let index = step / self.interval;
+            self.simulation.restore(&self.snapshots[index]);
+            self.position = index * self.interval;
+        }
+
+        while self.position < step && self.step() {}
+        self.position
+    }
+
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn is_done(&self) -> bool {
+        self.simulation.is_done()
+    }
+
+    fn render(&self) -> String {
+        self.simulation.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::{self, Display};
+
+    use super::{Player, Replay, Simulation};
+
+    /// Collatz sequence, which can't be computed backwards.
+    struct Collatz {
+        n: u64,
+        steps: usize,
+    }
+
+    impl Display for Collatz {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.n)
+        }
+    }
+
+    impl Simulation for Collatz {
+        type Snapshot = u64;
+
+        fn step(&mut self) {
+            self.n = if self.n.is_multiple_of(2) {
+                self.n / 2
+            } else {
+                3 * self.n + 1
+            };
+            self.steps += 1;
+        }
+
+        fn is_done(&self) -> bool {
+            self.n == 1
+        }
+
+        fn snapshot(&self) -> u64 {
+            self.n
+        }
+
+        fn restore(&mut self, snapshot: &u64) {
+            self.n = *snapshot;
+        }
+    }
+
+    fn replay() -> Replay<Collatz> {
+        Replay::with_interval(Collatz { n: 27, steps: 0 }, 10)
+    }
+
+    #[test]
+    fn stepping() {
+        let mut replay = replay();
+        assert_eq!(replay.render(), "27");
+        assert!(replay.step());
+        assert_eq!(replay.render(), "82");
+        assert_eq!(replay.position(), 1);
+
+        // 27 takes 111 steps to get to 1
+        assert_eq!(replay.seek(1000), 111);
+        assert!(replay.is_done());
+        assert!(!replay.step());
+        assert_eq!(replay.render(), "1");
+    }
+
+    #[test]
+    fn rewinding() {
+        let mut forwards = replay();
+        let frames: Vec<_> = (0..=111)
+            .map(|step| {
+                forwards.seek(step);
+                forwards.render()
+            })
+            .collect();
+
+        let mut backwards = replay();
+        backwards.seek(111);
+        for step in (0..=111).rev() {
+            assert_eq!(backwards.seek(step), step);
+            assert_eq!(backwards.render(), frames[step], "step {step}");
+        }
+
+        // going back restores the snapshot at the last multiple of 10, and replays the rest
+        let replayed: usize = (0..111).map(|step| step % 10).sum();
+        assert_eq!(backwards.simulation().steps, 111 + replayed);
+    }
+}
@@ -0,0 +1,116 @@
+//! Snapshot testing for rendered grids and simulation states.
+//!
+//! [assert_snapshot] compares a rendered value against `src/snapshots/<module>__<name>.snap`,
+//! showing a line diff on mismatch. Run the tests with `UPDATE_SNAPSHOTS=1` to write the current
+//! renders instead, then review the changes with `git diff`.
+
+use std::{env, fmt::Display, fs, path::PathBuf};
+
+/// Compares `$value`'s [Display] output against the snapshot named `$name` of the current module.
+macro_rules! assert_snapshot {
+    ($name:expr, $value:expr) => {
+        $crate::snapshot::check_snapshot(module_path!(), $name, &$value)
+    };
+}
+
+pub(crate) use assert_snapshot;
+
+fn snapshot_path(module_path: &str, name: &str) -> PathBuf {
+    let module = module_path
+        .split("::")
+        .skip(1)
+        .filter(|&part| part != "test")
+        .collect::<Vec<_>>()
+        .join("__");
+
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/snapshots")
+        .join(format!("{module}__{name}.snap"))
+}
+
+pub fn check_snapshot(module_path: &str, name: &str, value: &impl Display) {
+    let path = snapshot_path(module_path, name);
+    let mut actual = value.to_string();
+    if !actual.ends_with('\n') {
+        actual.push('\n');
+    }
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some_and(|update| update != "0") {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!(
+            "missing snapshot {}, run the tests with UPDATE_SNAPSHOTS=1 to create it:\n{actual}",
+            path.display()
+        );
+    };
+
+    if expected != actual {
+        panic!(
+            "snapshot {} doesn't match (- snapshot, + actual), run the tests with \
+             UPDATE_SNAPSHOTS=1 to update it:\n{}",
+            path.display(),
+            diff(&expected, &actual)
+        );
+    }
+}
+
+/// A line diff of the longest common subsequence of lines, with `-` for lines only in `before`
+/// and `+` for lines only in `after`.
+fn diff(before: &str, after: &str) -> String {
+    let before: Vec<_> = before.lines().collect();
+    let after: Vec<_> = after.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of before[i..] and after[j..]
+    let mut common = vec![vec![0; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            common[i][j] = if before[i] == after[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = vec![];
+    while i < before.len() || j < after.len() {
+        if i < before.len() && j < after.len() && before[i] == after[j] {
+            lines.push(format!("  {}", before[i]));
+            i += 1;
+            j += 1;
+        } else if i < before.len() && (j == after.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(format!("- {}", before[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", after[j]));
+            j += 1;
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn snapshot_path() {
+        assert!(
+            super::snapshot_path("advent_of_code_2024::day15::test", "map")
+                .ends_with("src/snapshots/day15__map.snap")
+        );
+    }
+
+    #[test]
+    fn diff() {
+        assert_eq!(
+            super::diff("#..\n.#.\n..#\n", "#..\n.@.\n..#\n"),
+            "  #..\n- .#.\n+ .@.\n  ..#"
+        );
+        assert_eq!(super::diff("a\n", "a\nb\n"), "  a\n+ b");
+    }
+}